@@ -1,7 +1,7 @@
-use std::{env, fmt::Debug};
+use std::fmt::Debug;
 
 use clap::{Arg, ArgAction, ArgMatches, Command};
-use kraken_rest_client::Client;
+use kraken_rest_client::{Client, Credentials};
 use serde::Serialize;
 // #todo What is a good name?
 pub fn add_json_args(command: Command) -> Command {
@@ -40,8 +40,11 @@ where
 
 // #todo find a better name.
 pub fn make_private_client() -> Client {
-    let api_key = env::var("KRAKEN_API_KEY").expect("KRAKEN_API_KEY must be set");
-    let api_secret = env::var("KRAKEN_API_SECRET").expect("KRAKEN_API_SECRET must be set");
+    let credentials =
+        Credentials::from_env().expect("KRAKEN_API_KEY and KRAKEN_API_SECRET must be set");
 
-    Client::new(api_key, api_secret)
+    Client::builder()
+        .credentials(credentials)
+        .try_build()
+        .expect("KRAKEN_API_SECRET must be valid base64")
 }
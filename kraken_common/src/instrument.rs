@@ -0,0 +1,365 @@
+//! Market identities shared by the spot REST, spot websocket v2 and futures
+//! APIs.
+//!
+//! Pair names without a separator, e.g. `XXBTZUSD`, `XBTUSD` or the `XBTUSD`
+//! in `PF_XBTUSD`, cannot be split into assets reliably without exchange
+//! metadata. They are resolved with a callback, e.g. one backed by the
+//! `SymbolRegistry` of `kraken_rest_client`.
+
+use std::fmt;
+use std::str::FromStr;
+
+/// Assets that are named differently in the websocket v2 API.
+const WS_V2_ASSET_NAMES: &[(&str, &str)] = &[("XBT", "BTC"), ("XDG", "DOGE")];
+
+/// Returns the websocket v2 name of an asset altname, e.g. `BTC` for `XBT`.
+pub fn ws_v2_asset_name(altname: &str) -> &str {
+    WS_V2_ASSET_NAMES
+        .iter()
+        .find(|(name, _)| *name == altname)
+        .map(|(_, ws_name)| *ws_name)
+        .unwrap_or(altname)
+}
+
+/// Returns the asset altname of a websocket v2 asset name, e.g. `XBT` for
+/// `BTC`.
+pub fn altname_from_ws_v2(ws_name: &str) -> &str {
+    WS_V2_ASSET_NAMES
+        .iter()
+        .find(|(_, name)| *name == ws_name)
+        .map(|(altname, _)| *altname)
+        .unwrap_or(ws_name)
+}
+
+/// A spot market, identified by its base and quote assets in websocket v2
+/// form, e.g. `BTC/USD`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SpotPair {
+    base: String,
+    quote: String,
+}
+
+impl SpotPair {
+    /// Creates a pair from asset altnames or websocket v2 names, e.g. `XBT`
+    /// or `BTC`.
+    pub fn new(base: &str, quote: &str) -> Self {
+        Self {
+            base: ws_v2_asset_name(&base.to_uppercase()).to_string(),
+            quote: ws_v2_asset_name(&quote.to_uppercase()).to_string(),
+        }
+    }
+
+    /// Parses a websocket v2 (`BTC/USD`) or v1 (`XBT/USD`) pair name.
+    pub fn from_ws(name: &str) -> Option<Self> {
+        let (base, quote) = name.split_once('/')?;
+
+        if base.is_empty() || quote.is_empty() || quote.contains('/') {
+            return None;
+        }
+
+        Some(Self::new(base, quote))
+    }
+
+    /// The base asset, in websocket v2 form.
+    pub fn base(&self) -> &str {
+        &self.base
+    }
+
+    /// The quote asset, in websocket v2 form.
+    pub fn quote(&self) -> &str {
+        &self.quote
+    }
+
+    /// The websocket v2 name, e.g. `BTC/USD`.
+    pub fn ws_name(&self) -> String {
+        format!("{}/{}", self.base, self.quote)
+    }
+
+    /// The REST altname, e.g. `XBTUSD`, accepted by all REST endpoints.
+    pub fn rest_name(&self) -> String {
+        format!(
+            "{}{}",
+            altname_from_ws_v2(&self.base),
+            altname_from_ws_v2(&self.quote)
+        )
+    }
+
+    /// The futures perpetual contract on this pair.
+    pub fn perpetual(&self, margin: FuturesMargin) -> FuturesContract {
+        FuturesContract {
+            margin,
+            underlying: self.clone(),
+            maturity: None,
+        }
+    }
+}
+
+impl fmt::Display for SpotPair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.base, self.quote)
+    }
+}
+
+impl FromStr for SpotPair {
+    type Err = String;
+
+    /// Parses a `BASE/QUOTE` name, see `from_ws`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_ws(s).ok_or_else(|| format!("Invalid pair: {}", s))
+    }
+}
+
+/// The websocket v2 name, so pairs can be passed as the `symbol` of the
+/// websocket requests.
+impl From<SpotPair> for String {
+    fn from(pair: SpotPair) -> Self {
+        pair.ws_name()
+    }
+}
+
+/// How a futures contract is margined and settled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum FuturesMargin {
+    /// Margined and settled in the base asset (`PI_`, `FI_`).
+    Inverse,
+    /// Margined and settled in multi-collateral, quoted in the quote asset
+    /// (`PF_`, `FF_`).
+    Linear,
+}
+
+/// A futures contract, identified by its margin type, underlying spot pair
+/// and, for fixed maturity contracts, its maturity date.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FuturesContract {
+    margin: FuturesMargin,
+    underlying: SpotPair,
+    /// Maturity date as `YYMMDD`, `None` for perpetual contracts.
+    maturity: Option<String>,
+}
+
+impl FuturesContract {
+    /// A fixed maturity contract, `maturity` given as `YYMMDD`.
+    pub fn fixed(margin: FuturesMargin, underlying: SpotPair, maturity: &str) -> Self {
+        Self {
+            margin,
+            underlying,
+            maturity: Some(maturity.to_string()),
+        }
+    }
+
+    /// Parses a futures symbol, e.g. `PF_XBTUSD`, `PI_ETHUSD` or
+    /// `FI_XBTUSD_230929`. The underlying pair name, e.g. `XBTUSD`, is
+    /// resolved with `resolve_pair`.
+    pub fn from_symbol(
+        symbol: &str,
+        resolve_pair: impl FnOnce(&str) -> Option<SpotPair>,
+    ) -> Option<Self> {
+        let symbol = symbol.to_uppercase();
+        let mut parts = symbol.split('_');
+
+        let (margin, is_perpetual) = match parts.next()? {
+            "PI" => (FuturesMargin::Inverse, true),
+            "PF" => (FuturesMargin::Linear, true),
+            "FI" => (FuturesMargin::Inverse, false),
+            "FF" => (FuturesMargin::Linear, false),
+            _ => return None,
+        };
+
+        let underlying = parts.next()?;
+        let maturity = parts.next().map(str::to_string);
+
+        if parts.next().is_some() || maturity.is_some() == is_perpetual {
+            return None;
+        }
+
+        if let Some(maturity) = &maturity {
+            if maturity.len() != 6 || !maturity.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+        }
+
+        Some(Self {
+            margin,
+            underlying: resolve_pair(underlying)?,
+            maturity,
+        })
+    }
+
+    /// The futures symbol, e.g. `PF_XBTUSD`.
+    pub fn symbol(&self) -> String {
+        let prefix = match (self.margin, self.maturity.is_some()) {
+            (FuturesMargin::Inverse, false) => "PI",
+            (FuturesMargin::Linear, false) => "PF",
+            (FuturesMargin::Inverse, true) => "FI",
+            (FuturesMargin::Linear, true) => "FF",
+        };
+
+        let mut symbol = format!("{}_{}", prefix, self.underlying.rest_name());
+
+        if let Some(maturity) = &self.maturity {
+            symbol.push('_');
+            symbol.push_str(maturity);
+        }
+
+        symbol
+    }
+
+    pub fn margin(&self) -> FuturesMargin {
+        self.margin
+    }
+
+    /// The spot pair this contract is written on.
+    pub fn underlying(&self) -> &SpotPair {
+        &self.underlying
+    }
+
+    pub fn maturity(&self) -> Option<&str> {
+        self.maturity.as_deref()
+    }
+
+    pub fn is_perpetual(&self) -> bool {
+        self.maturity.is_none()
+    }
+}
+
+impl fmt::Display for FuturesContract {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+/// A tradable instrument in any of the Kraken APIs.
+///
+/// Converts between the spot REST (`XBTUSD`), spot websocket v2 (`BTC/USD`)
+/// and futures (`PF_XBTUSD`) representations of a market.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Instrument {
+    Spot(SpotPair),
+    Futures(FuturesContract),
+}
+
+impl Instrument {
+    /// Parses a futures symbol, a websocket pair name or a spot REST pair
+    /// name. Pair names without a separator are resolved with `resolve_pair`.
+    pub fn parse(name: &str, resolve_pair: impl Fn(&str) -> Option<SpotPair>) -> Option<Self> {
+        let is_futures = matches!(
+            name.get(..3).map(str::to_uppercase).as_deref(),
+            Some("PI_" | "PF_" | "FI_" | "FF_")
+        );
+
+        if is_futures {
+            FuturesContract::from_symbol(name, resolve_pair).map(Self::Futures)
+        } else if name.contains('/') {
+            SpotPair::from_ws(name).map(Self::Spot)
+        } else {
+            resolve_pair(name).map(Self::Spot)
+        }
+    }
+
+    /// The spot market of the instrument, the underlying for futures.
+    pub fn spot_pair(&self) -> &SpotPair {
+        match self {
+            Self::Spot(pair) => pair,
+            Self::Futures(contract) => contract.underlying(),
+        }
+    }
+
+    /// Returns true if both instruments trade the same spot market, e.g. a
+    /// perpetual and its underlying pair.
+    pub fn same_market(&self, other: &Instrument) -> bool {
+        self.spot_pair() == other.spot_pair()
+    }
+}
+
+impl fmt::Display for Instrument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Spot(pair) => write!(f, "{}", pair),
+            Self::Futures(contract) => write!(f, "{}", contract),
+        }
+    }
+}
+
+impl From<SpotPair> for Instrument {
+    fn from(pair: SpotPair) -> Self {
+        Self::Spot(pair)
+    }
+}
+
+impl From<FuturesContract> for Instrument {
+    fn from(contract: FuturesContract) -> Self {
+        Self::Futures(contract)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Resolves the few pair names used in the tests.
+    fn resolve_pair(name: &str) -> Option<SpotPair> {
+        match name {
+            "XXBTZUSD" | "XBTUSD" => Some(SpotPair::new("XBT", "USD")),
+            "XETHZUSD" | "ETHUSD" => Some(SpotPair::new("ETH", "USD")),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn spot_pair_names() {
+        let pair: SpotPair = "BTC/USD".parse().unwrap();
+
+        assert_eq!(pair.ws_name(), "BTC/USD");
+        assert_eq!(pair.rest_name(), "XBTUSD");
+        assert_eq!(SpotPair::from_ws("XBT/USD"), Some(pair.clone()));
+        assert_eq!(SpotPair::new("xdg", "usdt").ws_name(), "DOGE/USDT");
+        assert_eq!(String::from(pair), "BTC/USD");
+
+        assert!(SpotPair::from_ws("XBTUSD").is_none());
+        assert!(SpotPair::from_ws("BTC/").is_none());
+    }
+
+    #[test]
+    fn futures_symbols() {
+        let perpetual = FuturesContract::from_symbol("PF_XBTUSD", resolve_pair).unwrap();
+
+        assert_eq!(perpetual.margin(), FuturesMargin::Linear);
+        assert!(perpetual.is_perpetual());
+        assert_eq!(perpetual.underlying().ws_name(), "BTC/USD");
+        assert_eq!(perpetual.symbol(), "PF_XBTUSD");
+
+        let fixed = FuturesContract::from_symbol("fi_ethusd_230929", resolve_pair).unwrap();
+
+        assert_eq!(fixed.margin(), FuturesMargin::Inverse);
+        assert_eq!(fixed.maturity(), Some("230929"));
+        assert_eq!(fixed.symbol(), "FI_ETHUSD_230929");
+
+        assert!(FuturesContract::from_symbol("PF_XBTUSD_230929", resolve_pair).is_none());
+        assert!(FuturesContract::from_symbol("FI_XBTUSD", resolve_pair).is_none());
+        assert!(FuturesContract::from_symbol("IN_XBTUSD", resolve_pair).is_none());
+        assert!(FuturesContract::from_symbol("PF_PYTHUSD", resolve_pair).is_none());
+
+        assert_eq!(
+            SpotPair::new("XBT", "USD")
+                .perpetual(FuturesMargin::Inverse)
+                .symbol(),
+            "PI_XBTUSD"
+        );
+    }
+
+    #[test]
+    fn instruments_on_the_same_market() {
+        let spot = Instrument::parse("XXBTZUSD", resolve_pair).unwrap();
+        let ws = Instrument::parse("BTC/USD", resolve_pair).unwrap();
+        let futures = Instrument::parse("PI_XBTUSD", resolve_pair).unwrap();
+        let other = Instrument::parse("PF_ETHUSD", resolve_pair).unwrap();
+
+        assert_eq!(spot, ws);
+        assert!(matches!(futures, Instrument::Futures(_)));
+        assert!(futures.same_market(&spot));
+        assert!(!other.same_market(&spot));
+        assert_eq!(futures.to_string(), "PI_XBTUSD");
+        assert_eq!(spot.to_string(), "BTC/USD");
+        assert!(Instrument::parse("PYTHUSD", resolve_pair).is_none());
+    }
+}
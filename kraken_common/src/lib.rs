@@ -0,0 +1,7 @@
+pub mod instrument;
+pub mod secret;
+pub mod url;
+
+pub use instrument::{FuturesContract, FuturesMargin, Instrument, SpotPair};
+pub use secret::SecretString;
+pub use url::validate_base_url;
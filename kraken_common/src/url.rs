@@ -0,0 +1,24 @@
+//! Validation of the API base URLs passed to the client builders.
+
+use url::Url;
+
+/// Checks that the base URL is an absolute http(s) URL and strips any
+/// trailing slash, as request paths start with one. Returns the reason if the
+/// URL is invalid.
+pub fn validate_base_url(url: &str) -> Result<&str, String> {
+    let parsed = Url::parse(url).map_err(|err| err.to_string())?;
+
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err("the scheme must be http or https".to_string());
+    }
+
+    if parsed.host_str().is_none() {
+        return Err("missing host".to_string());
+    }
+
+    if parsed.query().is_some() || parsed.fragment().is_some() {
+        return Err("must not contain a query or fragment".to_string());
+    }
+
+    Ok(url.trim_end_matches('/'))
+}
@@ -26,6 +26,11 @@ impl GetInstrumentsRequest {
     pub async fn send(self) -> Result<GetInstrumentsResponse> {
         self.execute().await
     }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<GetInstrumentsResponse> {
+        crate::blocking::block_on(self.send())
+    }
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -70,13 +75,37 @@ impl Client {
 #[cfg(test)]
 mod tests {
     use crate::Client;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
 
     #[tokio::test]
     async fn get_instruments_fetches_the_supported_instruments() {
-        let client = Client::default();
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/derivatives/api/v3/instruments"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                include_str!("../../fixtures/instruments.json"),
+                "application/json",
+            ))
+            .mount(&server)
+            .await;
+
+        let client = Client::builder().base_url(&server.uri()).build();
+
+        let res = client.get_instruments().send().await.unwrap();
+
+        assert_eq!(res.result, "success");
+        assert_eq!(res.instruments.len(), 2);
 
-        let res = client.get_instruments().send().await;
+        let instrument = &res.instruments[0];
 
-        dbg!(&res);
+        assert_eq!(instrument.symbol, "PI_XBTUSD");
+        assert_eq!(instrument.tick_size, Some(0.5));
+        assert_eq!(
+            instrument.margin_levels.as_ref().unwrap()[1].contracts,
+            Some(500000)
+        );
+        assert!(!res.instruments[1].tradeable);
     }
 }
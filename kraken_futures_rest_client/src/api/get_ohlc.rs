@@ -111,6 +111,11 @@ impl GetOhlcRequest {
     pub async fn send(self) -> Result<GetOhlcResponse> {
         self.execute().await
     }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<GetOhlcResponse> {
+        crate::blocking::block_on(self.send())
+    }
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -151,20 +156,37 @@ impl Client {
 mod tests {
     use super::{Interval, PriceType};
     use crate::Client;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
 
     #[tokio::test]
     async fn get_ohlc_fetches_candles() {
-        let client = Client::default();
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/charts/v1/mark/PI_XBTUSD/1m"))
+            .and(query_param("from", "1688671200"))
+            .and(query_param("to", "1688671320"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_raw(include_str!("../../fixtures/ohlc.json"), "application/json"),
+            )
+            .mount(&server)
+            .await;
 
-        let from = chrono::Local::now();
-        let from = from - chrono::Duration::hours(1);
+        let client = Client::builder().base_url(&server.uri()).build();
 
         let res = client
             .get_ohlc("PI_XBTUSD", Interval::Min1, PriceType::Mark)
-            .from(from.timestamp())
+            .from(1688671200)
+            .to(1688671320)
             .send()
-            .await;
+            .await
+            .unwrap();
 
-        dbg!(&res);
+        assert_eq!(res.candles.len(), 2);
+        assert_eq!(res.candles[0].time, 1688671200000);
+        assert_eq!(res.candles[1].close, "30300.0");
+        assert!(!res.more_candles);
     }
 }
@@ -0,0 +1,41 @@
+//! Support for synchronous programs, enabled with the `blocking` feature.
+//!
+//! Every request builder gets a `send_blocking` method that sends the request
+//! and waits for the response on a runtime owned by the calling thread:
+//!
+//! ```no_run
+//! let client = kraken_futures_rest_client::Client::default();
+//!
+//! let instruments = client.get_instruments().send_blocking().unwrap();
+//! ```
+//!
+//! Calling `send_blocking` from async code panics, use `send` instead.
+
+use std::future::Future;
+
+use tokio::runtime::{Builder, Runtime};
+
+thread_local! {
+    static RUNTIME: Runtime = Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("failed to build the blocking runtime");
+}
+
+/// Runs a future to completion on the runtime of the current thread.
+pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+    RUNTIME.with(|runtime| runtime.block_on(future))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Client;
+
+    #[test]
+    fn send_blocking_without_runtime() {
+        // Nothing listens on port 1, so the request fails fast.
+        let client = Client::builder().base_url("http://127.0.0.1:1").build();
+
+        assert!(client.get_instruments().send_blocking().is_err());
+    }
+}
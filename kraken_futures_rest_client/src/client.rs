@@ -1,4 +1,6 @@
-use crate::error::Error;
+use crate::error::{BuildError, Error};
+use base64::{engine::general_purpose::STANDARD as base64_engine, Engine as _};
+use kraken_common::{validate_base_url, SecretString};
 use reqwest::header;
 use serde::de::DeserializeOwned;
 use std::time::Duration;
@@ -11,12 +13,18 @@ const DEFAULT_BASE_URL: &str = "https://futures.kraken.com";
 
 const DEFAULT_USER_AGENT: &str = "rust-kraken-futures-client/0.2";
 
-#[derive(Default)]
+/// The environment variable holding the public key, read by `auth_from_env`.
+pub const PUBLIC_KEY_VAR: &str = "KRAKEN_FUTURES_PUBLIC_KEY";
+
+/// The environment variable holding the private key, read by `auth_from_env`.
+pub const PRIVATE_KEY_VAR: &str = "KRAKEN_FUTURES_PRIVATE_KEY";
+
+#[derive(Debug, Default)]
 pub struct ClientBuilder {
     base_url: Option<String>,
     user_agent: Option<String>,
     public_key: Option<String>,
-    private_key: Option<String>,
+    private_key: Option<SecretString>,
     http_client: Option<reqwest::Client>,
     timeout: Option<Duration>,
 }
@@ -38,16 +46,32 @@ impl ClientBuilder {
     }
 
     pub fn private_key(mut self, private_key: &str) -> Self {
-        self.private_key = Some(private_key.to_string());
+        self.private_key = Some(SecretString::new(private_key));
         self
     }
 
     pub fn auth(mut self, public_key: &str, private_key: &str) -> Self {
         self.public_key = Some(public_key.to_string());
-        self.private_key = Some(private_key.to_string());
+        self.private_key = Some(SecretString::new(private_key));
         self
     }
 
+    /// Reads the keys from the `KRAKEN_FUTURES_PUBLIC_KEY` and
+    /// `KRAKEN_FUTURES_PRIVATE_KEY` environment variables.
+    pub fn auth_from_env(self) -> Result<Self> {
+        let read =
+            |var: &str| std::env::var(var).map_err(|err| Error::internal(format!("{var}: {err}")));
+
+        let public_key = read(PUBLIC_KEY_VAR)?;
+        let private_key = SecretString::new(read(PRIVATE_KEY_VAR)?);
+
+        Ok(Self {
+            public_key: Some(public_key),
+            private_key: Some(private_key),
+            ..self
+        })
+    }
+
     pub fn http_client(mut self, http_client: reqwest::Client) -> Self {
         self.http_client = Some(http_client);
         self
@@ -58,22 +82,77 @@ impl ClientBuilder {
         self
     }
 
-    pub fn build(self) -> Client {
+    /// Validates the configuration and builds the client.
+    pub fn try_build(mut self) -> std::result::Result<Client, BuildError> {
+        let base_url = self.base_url.as_deref().unwrap_or(DEFAULT_BASE_URL);
+        let base_url = validate_base_url(base_url)
+            .map_err(|reason| BuildError::InvalidBaseUrl {
+                url: base_url.to_string(),
+                reason,
+            })?
+            .to_string();
+
+        match (&self.public_key, &self.private_key) {
+            (Some(_), Some(private_key)) => {
+                base64_engine
+                    .decode(private_key.expose())
+                    .map_err(|err| BuildError::InvalidPrivateKey(err.to_string()))?;
+            }
+            (None, None) => {}
+            _ => return Err(BuildError::IncompleteCredentials),
+        }
+
+        let http_client = match self.http_client.take() {
+            Some(http_client) => http_client,
+            None => {
+                let timeout = self.timeout.unwrap_or(DEFAULT_TIMEOUT);
+
+                if timeout.is_zero() {
+                    return Err(BuildError::InvalidTimeout);
+                }
+
+                reqwest::Client::builder()
+                    .timeout(timeout)
+                    .build()
+                    .map_err(|err| BuildError::HttpClient(err.to_string()))?
+            }
+        };
+
+        Ok(self.into_client(base_url, http_client))
+    }
+
+    /// Builds the client without validating the configuration, use
+    /// `try_build` to catch configuration errors early.
+    ///
+    /// Panics if the http client cannot be built, e.g. when the TLS backend
+    /// fails to initialize.
+    pub fn build(mut self) -> Client {
+        let base_url = self
+            .base_url
+            .as_deref()
+            .unwrap_or(DEFAULT_BASE_URL)
+            .trim_end_matches('/')
+            .to_string();
+
+        let http_client = self.http_client.take().unwrap_or_else(|| {
+            reqwest::Client::builder()
+                .timeout(self.timeout.unwrap_or(DEFAULT_TIMEOUT))
+                .build()
+                .expect("failed to build the http client")
+        });
+
+        self.into_client(base_url, http_client)
+    }
+
+    fn into_client(self, base_url: String, http_client: reqwest::Client) -> Client {
         Client {
-            base_url: self
-                .base_url
-                .unwrap_or_else(|| DEFAULT_BASE_URL.to_string()),
+            base_url,
             user_agent: self
                 .user_agent
                 .unwrap_or_else(|| DEFAULT_USER_AGENT.to_string()),
             public_key: self.public_key,
             private_key: self.private_key,
-            http_client: self.http_client.unwrap_or_else(|| {
-                reqwest::Client::builder()
-                    .timeout(self.timeout.unwrap_or(DEFAULT_TIMEOUT))
-                    .build()
-                    .unwrap()
-            }),
+            http_client,
         }
     }
 }
@@ -85,7 +164,7 @@ pub struct Client {
     #[allow(dead_code)]
     public_key: Option<String>,
     #[allow(dead_code)]
-    private_key: Option<String>,
+    private_key: Option<SecretString>,
     http_client: reqwest::Client,
 }
 
@@ -172,3 +251,42 @@ impl Client {
     //     self.unwrap_response(resp).await
     // }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{BuildError, Client};
+    use std::time::Duration;
+
+    #[test]
+    fn try_build_validates_configuration() {
+        assert!(Client::builder()
+            .base_url("http://localhost:8080/")
+            .try_build()
+            .is_ok());
+
+        assert!(matches!(
+            Client::builder().base_url("futures.kraken.com").try_build(),
+            Err(BuildError::InvalidBaseUrl { .. })
+        ));
+        assert!(matches!(
+            Client::builder().public_key("my-public-key").try_build(),
+            Err(BuildError::IncompleteCredentials)
+        ));
+        assert!(matches!(
+            Client::builder()
+                .auth("my-public-key", "not base64!")
+                .try_build(),
+            Err(BuildError::InvalidPrivateKey(_))
+        ));
+        assert!(matches!(
+            Client::builder().timeout(Duration::ZERO).try_build(),
+            Err(BuildError::InvalidTimeout)
+        ));
+
+        // `build` does not validate the base URL or the credentials.
+        Client::builder()
+            .base_url("futures.kraken.com")
+            .auth("my-public-key", "not base64!")
+            .build();
+    }
+}
@@ -14,6 +14,21 @@ pub enum Error {
     Api(String),
 }
 
+/// Errors returned by `ClientBuilder::try_build`.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum BuildError {
+    #[error("invalid base url `{url}`: {reason}")]
+    InvalidBaseUrl { url: String, reason: String },
+    #[error("the public and private key must be set together")]
+    IncompleteCredentials,
+    #[error("invalid private key, expected base64: {0}")]
+    InvalidPrivateKey(String),
+    #[error("the timeout must be greater than zero")]
+    InvalidTimeout,
+    #[error("failed to build the http client: {0}")]
+    HttpClient(String),
+}
+
 impl Error {
     #[allow(dead_code)]
     pub(crate) fn internal(message: impl fmt::Display) -> Self {
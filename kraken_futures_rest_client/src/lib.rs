@@ -1,5 +1,9 @@
 pub mod api;
+#[cfg(feature = "blocking")]
+pub mod blocking;
 pub mod client;
 pub mod error;
 
 pub use client::{Client, Result};
+pub use error::{BuildError, Error};
+pub use kraken_common::instrument::{FuturesContract, FuturesMargin, SpotPair};
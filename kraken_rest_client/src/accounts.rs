@@ -0,0 +1,320 @@
+//! Helpers to manage a master account and its subaccounts.
+//!
+//! Transfers between accounts are always requested by the master account and
+//! address the accounts by IIBAN. `MultiAccountClient` keeps a `Client` and
+//! the IIBAN of every account under a name, so funds can be moved and
+//! rebalanced between strategy accounts by name.
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use futures::future::try_join_all;
+use rust_decimal::Decimal;
+
+use crate::{
+    api::{AccountTransferResponse, CreateSubaccountRequest},
+    error::Error,
+    Client, Result,
+};
+
+/// The name under which the master account is registered.
+pub const MASTER_ACCOUNT: &str = "master";
+
+#[derive(Debug, Clone)]
+struct Account {
+    client: Client,
+    iiban: String,
+}
+
+/// A transfer planned by `MultiAccountClient::plan_rebalance`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlannedTransfer {
+    pub from: String,
+    pub to: String,
+    pub amount: Decimal,
+}
+
+/// Holds the credentials of a master account and its subaccounts.
+#[derive(Debug, Clone)]
+pub struct MultiAccountClient {
+    master: Account,
+    subaccounts: BTreeMap<String, Account>,
+}
+
+impl MultiAccountClient {
+    /// Creates a client for the given master account, registered as
+    /// `MASTER_ACCOUNT`.
+    pub fn new(master: Client, master_iiban: impl Into<String>) -> Self {
+        Self {
+            master: Account {
+                client: master,
+                iiban: master_iiban.into(),
+            },
+            subaccounts: BTreeMap::new(),
+        }
+    }
+
+    /// Registers a subaccount under `name`, which must not be
+    /// `MASTER_ACCOUNT`.
+    pub fn subaccount(
+        mut self,
+        name: impl Into<String>,
+        client: Client,
+        iiban: impl Into<String>,
+    ) -> Result<Self> {
+        let name = name.into();
+
+        if name == MASTER_ACCOUNT {
+            return Err(Error::account(format!(
+                "`{MASTER_ACCOUNT}` is reserved for the master account"
+            )));
+        }
+
+        self.subaccounts.insert(
+            name,
+            Account {
+                client,
+                iiban: iiban.into(),
+            },
+        );
+
+        Ok(self)
+    }
+
+    pub fn master(&self) -> &Client {
+        &self.master.client
+    }
+
+    /// Returns the client of the account registered under `name`.
+    pub fn client(&self, name: &str) -> Option<&Client> {
+        self.find(name).map(|account| &account.client)
+    }
+
+    /// Returns the IIBAN of the account registered under `name`.
+    pub fn iiban(&self, name: &str) -> Option<&str> {
+        self.find(name).map(|account| account.iiban.as_str())
+    }
+
+    /// Returns the names of all registered accounts, including the master.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.accounts().map(|(name, _)| name)
+    }
+
+    /// Returns all registered accounts, starting with the master.
+    fn accounts(&self) -> impl Iterator<Item = (&str, &Account)> {
+        std::iter::once((MASTER_ACCOUNT, &self.master)).chain(
+            self.subaccounts
+                .iter()
+                .map(|(name, account)| (name.as_str(), account)),
+        )
+    }
+
+    fn find(&self, name: &str) -> Option<&Account> {
+        if name == MASTER_ACCOUNT {
+            Some(&self.master)
+        } else {
+            self.subaccounts.get(name)
+        }
+    }
+
+    fn account(&self, name: &str) -> Result<&Account> {
+        self.find(name)
+            .ok_or_else(|| Error::account(format!("unknown account `{name}`")))
+    }
+
+    /// Creates a new subaccount through the master account. The subaccount
+    /// has to be registered with `subaccount` once its API key is available.
+    pub fn create_subaccount(
+        &self,
+        username: impl Into<String>,
+        email: impl Into<String>,
+    ) -> CreateSubaccountRequest {
+        self.master().create_subaccount(username, email)
+    }
+
+    /// Transfers `amount` of `asset` between two registered accounts.
+    pub async fn transfer(
+        &self,
+        asset: &str,
+        amount: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<AccountTransferResponse> {
+        let from = &self.account(from)?.iiban;
+        let to = &self.account(to)?.iiban;
+
+        self.master()
+            .account_transfer(asset, amount, from, to)
+            .send()
+            .await
+    }
+
+    /// Returns the balance of `asset` in every registered account.
+    pub async fn balances(&self, asset: &str) -> Result<BTreeMap<String, Decimal>> {
+        let balances = try_join_all(self.accounts().map(|(name, account)| async move {
+            let balance = account.client.get_account_balance().send().await?;
+
+            let amount = match balance.get(asset) {
+                Some(amount) => Decimal::from_str(amount).map_err(Error::internal)?,
+                None => Decimal::ZERO,
+            };
+
+            Ok::<_, Error>((name.to_string(), amount))
+        }))
+        .await?;
+
+        Ok(balances.into_iter().collect())
+    }
+
+    /// Plans the transfers that move the balances of the accounts in
+    /// `targets` to their target amounts. Accounts above their target send
+    /// the surplus to the accounts below their target. Accounts without a
+    /// target are left untouched.
+    pub fn plan_rebalance(
+        balances: &BTreeMap<String, Decimal>,
+        targets: &BTreeMap<String, Decimal>,
+    ) -> Result<Vec<PlannedTransfer>> {
+        let mut surplus = Vec::new();
+        let mut deficit = Vec::new();
+
+        for (name, target) in targets {
+            let balance = balances.get(name).copied().unwrap_or_default();
+
+            if balance > *target {
+                surplus.push((name.clone(), balance - target));
+            } else if balance < *target {
+                deficit.push((name.clone(), target - balance));
+            }
+        }
+
+        let available: Decimal = surplus.iter().map(|(_, amount)| amount).sum();
+        let required: Decimal = deficit.iter().map(|(_, amount)| amount).sum();
+
+        if available < required {
+            return Err(Error::account(format!(
+                "insufficient funds to rebalance, {available} available but {required} required"
+            )));
+        }
+
+        let mut transfers = Vec::new();
+        let mut surplus = surplus.into_iter().peekable();
+
+        for (to, mut needed) in deficit {
+            while !needed.is_zero() {
+                let Some((from, left)) = surplus.peek_mut() else {
+                    break;
+                };
+
+                let amount = needed.min(*left);
+
+                transfers.push(PlannedTransfer {
+                    from: from.clone(),
+                    to: to.clone(),
+                    amount,
+                });
+
+                needed -= amount;
+                *left -= amount;
+
+                if left.is_zero() {
+                    surplus.next();
+                }
+            }
+        }
+
+        Ok(transfers)
+    }
+
+    /// Moves funds between the accounts so that the balance of `asset` in
+    /// each account of `targets` matches its target amount. Transfers are
+    /// executed sequentially.
+    pub async fn rebalance(
+        &self,
+        asset: &str,
+        targets: &BTreeMap<String, Decimal>,
+    ) -> Result<Vec<AccountTransferResponse>> {
+        for name in targets.keys() {
+            self.account(name)?;
+        }
+
+        let balances = self.balances(asset).await?;
+
+        let mut responses = Vec::new();
+
+        for transfer in Self::plan_rebalance(&balances, targets)? {
+            let amount = transfer.amount.normalize().to_string();
+
+            responses.push(
+                self.transfer(asset, &amount, &transfer.from, &transfer.to)
+                    .await?,
+            );
+        }
+
+        Ok(responses)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MultiAccountClient, PlannedTransfer, MASTER_ACCOUNT};
+    use crate::{Client, Error};
+    use rust_decimal::Decimal;
+    use std::collections::BTreeMap;
+
+    fn amounts(entries: &[(&str, i64)]) -> BTreeMap<String, Decimal> {
+        entries
+            .iter()
+            .map(|(name, amount)| (name.to_string(), Decimal::from(*amount)))
+            .collect()
+    }
+
+    #[test]
+    fn plan_rebalance_moves_surplus_to_deficits() {
+        let balances = amounts(&[("master", 100), ("alpha", 10), ("beta", 0)]);
+        let targets = amounts(&[("master", 40), ("alpha", 30), ("beta", 40)]);
+
+        let transfers = MultiAccountClient::plan_rebalance(&balances, &targets).unwrap();
+
+        assert_eq!(
+            transfers,
+            vec![
+                PlannedTransfer {
+                    from: "master".into(),
+                    to: "alpha".into(),
+                    amount: Decimal::from(20),
+                },
+                PlannedTransfer {
+                    from: "master".into(),
+                    to: "beta".into(),
+                    amount: Decimal::from(40),
+                },
+            ]
+        );
+
+        let targets = amounts(&[("master", 100), ("beta", 40)]);
+
+        assert!(matches!(
+            MultiAccountClient::plan_rebalance(&balances, &targets),
+            Err(Error::Account(_))
+        ));
+    }
+
+    #[test]
+    fn subaccount_rejects_the_master_name() {
+        let accounts = MultiAccountClient::new(Client::default(), "AA25 N84G 6KFB 4ZQP");
+
+        assert!(matches!(
+            accounts
+                .clone()
+                .subaccount(MASTER_ACCOUNT, Client::default(), "AA72 N84G 6KFB 4ZQP"),
+            Err(Error::Account(_))
+        ));
+
+        let accounts = accounts
+            .subaccount("alpha", Client::default(), "AA72 N84G 6KFB 4ZQP")
+            .unwrap();
+
+        assert_eq!(accounts.names().collect::<Vec<_>>(), ["master", "alpha"]);
+        assert_eq!(accounts.iiban("master"), Some("AA25 N84G 6KFB 4ZQP"));
+    }
+}
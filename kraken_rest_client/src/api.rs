@@ -24,6 +24,9 @@ pub use get_order_book::*;
 pub mod get_recent_trades;
 pub use get_recent_trades::*;
 
+pub mod get_recent_spreads;
+pub use get_recent_spreads::*;
+
 // endregion: public
 
 // region: private
@@ -49,6 +52,9 @@ pub use get_ledgers::*;
 pub mod get_account_balance;
 pub use get_account_balance::*;
 
+pub mod get_extended_balance;
+pub use get_extended_balance::*;
+
 pub mod get_trade_balance;
 pub use get_trade_balance::*;
 
@@ -67,17 +73,61 @@ pub use get_trades_history::*;
 pub mod query_orders_info;
 pub use query_orders_info::*;
 
+pub mod query_trades;
+pub use query_trades::*;
+
+pub mod query_ledgers;
+pub use query_ledgers::*;
+
 pub mod get_open_positions;
 pub use get_open_positions::*;
 
 pub mod withdraw;
 pub use withdraw::*;
 
+pub mod get_withdrawal_info;
+pub use get_withdrawal_info::*;
+
+pub mod get_withdrawal_status;
+pub use get_withdrawal_status::*;
+
+pub mod cancel_withdrawal;
+pub use cancel_withdrawal::*;
+
+pub mod wallet_transfer;
+pub use wallet_transfer::*;
+
+// User Data Exports
+
+pub mod add_export;
+pub use add_export::*;
+
+pub mod get_export_status;
+pub use get_export_status::*;
+
+pub mod retrieve_export;
+pub use retrieve_export::*;
+
+pub mod remove_export;
+pub use remove_export::*;
+
 // User Trading
 
 pub mod add_order;
 pub use add_order::*;
 
+pub mod add_order_batch;
+pub use add_order_batch::*;
+
+pub mod edit_order;
+pub use edit_order::*;
+
+pub mod amend_order;
+pub use amend_order::*;
+
+pub mod get_order_amends;
+pub use get_order_amends::*;
+
 pub mod cancel_order;
 pub use cancel_order::*;
 
@@ -87,7 +137,35 @@ pub use cancel_all_orders::*;
 pub mod cancel_order_batch;
 pub use cancel_order_batch::*;
 
-// User Staking
+// User Subaccounts
+
+pub mod create_subaccount;
+pub use create_subaccount::*;
+
+pub mod account_transfer;
+pub use account_transfer::*;
+
+// User Earn
+
+pub mod list_earn_strategies;
+pub use list_earn_strategies::*;
+
+pub mod list_earn_allocations;
+pub use list_earn_allocations::*;
+
+pub mod allocate_earn_funds;
+pub use allocate_earn_funds::*;
+
+pub mod deallocate_earn_funds;
+pub use deallocate_earn_funds::*;
+
+pub mod get_earn_allocation_status;
+pub use get_earn_allocation_status::*;
+
+pub mod get_earn_deallocation_status;
+pub use get_earn_deallocation_status::*;
+
+// User Staking (deprecated, use the Earn endpoints instead)
 
 pub mod stake_asset;
 pub use stake_asset::*;
@@ -104,3 +182,6 @@ pub mod get_web_sockets_token;
 pub use get_web_sockets_token::*;
 
 // endregion: private
+
+#[cfg(test)]
+mod tests;
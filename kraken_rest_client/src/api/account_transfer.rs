@@ -0,0 +1,100 @@
+use crate::{Client, Result};
+use serde::{de::DeserializeOwned, Deserialize};
+
+/// Transfer funds to and from master and subaccounts. Must be called with an
+/// API key of the master account.
+///
+/// - <https://docs.kraken.com/rest/#tag/Subaccounts/operation/accountTransfer>
+/// - <https://api.kraken.com/0/private/AccountTransfer>
+#[must_use = "Does nothing until you send or execute it"]
+pub struct AccountTransferRequest {
+    client: Client,
+    /// Asset being transferred
+    asset: String,
+    /// Amount of asset to transfer
+    amount: String,
+    /// IIBAN of the source account
+    from: String,
+    /// IIBAN of the destination account
+    to: String,
+}
+
+impl AccountTransferRequest {
+    pub async fn execute<T: DeserializeOwned>(self) -> Result<T> {
+        let query = format!(
+            "asset={}&amount={}&from={}&to={}",
+            urlencoding::encode(&self.asset),
+            urlencoding::encode(&self.amount),
+            urlencoding::encode(&self.from),
+            urlencoding::encode(&self.to),
+        );
+
+        self.client
+            .send_private("/0/private/AccountTransfer", Some(query))
+            .await
+    }
+
+    pub async fn send(self) -> Result<AccountTransferResponse> {
+        self.execute().await
+    }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<AccountTransferResponse> {
+        crate::blocking::block_on(self.send())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AccountTransferStatus {
+    Pending,
+    Complete,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AccountTransferResponse {
+    /// Transfer ID
+    pub transfer_id: String,
+    /// Transfer status
+    pub status: AccountTransferStatus,
+}
+
+impl Client {
+    /// Transfers `amount` of `asset` between the accounts with the given
+    /// IIBANs.
+    pub fn account_transfer(
+        &self,
+        asset: impl Into<String>,
+        amount: impl Into<String>,
+        from: impl Into<String>,
+        to: impl Into<String>,
+    ) -> AccountTransferRequest {
+        AccountTransferRequest {
+            client: self.clone(),
+            asset: asset.into(),
+            amount: amount.into(),
+            from: from.into(),
+            to: to.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AccountTransferResponse, AccountTransferStatus};
+
+    #[test]
+    fn deserialize_account_transfer() {
+        let json = r#"{
+            "transfer_id": "TOH3AS2-LPCWR8-JDQGEU",
+            "status": "complete"
+        }"#;
+
+        let resp: AccountTransferResponse = serde_json::from_str(json).unwrap();
+
+        assert_eq!(resp.transfer_id, "TOH3AS2-LPCWR8-JDQGEU");
+        assert_eq!(resp.status, AccountTransferStatus::Complete);
+    }
+}
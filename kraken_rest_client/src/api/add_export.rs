@@ -0,0 +1,157 @@
+use crate::{Client, Result};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportReport {
+    Trades,
+    Ledgers,
+}
+
+impl fmt::Display for ExportReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let report = match self {
+            Self::Trades => "trades",
+            Self::Ledgers => "ledgers",
+        };
+
+        write!(f, "{}", report)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum ExportFormat {
+    Csv,
+    Tsv,
+}
+
+impl fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let format = match self {
+            Self::Csv => "CSV",
+            Self::Tsv => "TSV",
+        };
+
+        write!(f, "{}", format)
+    }
+}
+
+/// Request export of trades or ledgers. The report is generated
+/// asynchronously, use `get_export_status` to poll for completion and
+/// `retrieve_export` to download it.
+///
+/// - <https://docs.kraken.com/rest/#tag/Account-Data/operation/addExport>
+/// - <https://api.kraken.com/0/private/AddExport>
+#[must_use = "Does nothing until you send or execute it"]
+pub struct AddExportRequest {
+    client: Client,
+    /// Type of data to export
+    report: ExportReport,
+    /// Description for the export
+    description: String,
+    /// File format to export (default = CSV)
+    format: Option<ExportFormat>,
+    /// Comma-delimited list of fields to include (default = all)
+    fields: Option<String>,
+    /// UNIX timestamp for report start time (default 1st of the current month)
+    starttm: Option<i64>,
+    /// UNIX timestamp for report end time (default now)
+    endtm: Option<i64>,
+}
+
+impl AddExportRequest {
+    pub fn format(self, format: ExportFormat) -> Self {
+        Self {
+            format: Some(format),
+            ..self
+        }
+    }
+
+    /// The fields to include in the report, e.g. `["refid", "time", "amount"]`
+    /// for ledgers. All fields are included by default.
+    pub fn fields<S: AsRef<str>>(self, fields: &[S]) -> Self {
+        let fields: Vec<&str> = fields.iter().map(AsRef::as_ref).collect();
+
+        Self {
+            fields: Some(fields.join(",")),
+            ..self
+        }
+    }
+
+    pub fn starttm(self, starttm: i64) -> Self {
+        Self {
+            starttm: Some(starttm),
+            ..self
+        }
+    }
+
+    pub fn endtm(self, endtm: i64) -> Self {
+        Self {
+            endtm: Some(endtm),
+            ..self
+        }
+    }
+
+    pub async fn execute<T: DeserializeOwned>(self) -> Result<T> {
+        let mut query = format!(
+            "report={}&description={}",
+            self.report,
+            urlencoding::encode(&self.description)
+        );
+
+        if let Some(format) = self.format {
+            query.push_str(&format!("&format={}", format));
+        }
+
+        if let Some(fields) = &self.fields {
+            query.push_str(&format!("&fields={}", fields));
+        }
+
+        if let Some(starttm) = self.starttm {
+            query.push_str(&format!("&starttm={}", starttm));
+        }
+
+        if let Some(endtm) = self.endtm {
+            query.push_str(&format!("&endtm={}", endtm));
+        }
+
+        self.client
+            .send_private("/0/private/AddExport", Some(query))
+            .await
+    }
+
+    pub async fn send(self) -> Result<AddExportResponse> {
+        self.execute().await
+    }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<AddExportResponse> {
+        crate::blocking::block_on(self.send())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AddExportResponse {
+    /// Report ID
+    pub id: String,
+}
+
+impl Client {
+    pub fn add_export(
+        &self,
+        report: ExportReport,
+        description: impl Into<String>,
+    ) -> AddExportRequest {
+        AddExportRequest {
+            client: self.clone(),
+            report,
+            description: description.into(),
+            format: None,
+            fields: None,
+            starttm: None,
+            endtm: None,
+        }
+    }
+}
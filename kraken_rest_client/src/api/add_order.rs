@@ -1,16 +1,22 @@
 use crate::{
-    types::{OrderSide, OrderType},
+    error::Error,
+    types::{
+        OrderFlag, OrderFlags, OrderSide, OrderType, SelfTradePrevention, TimeInForce, Trigger,
+    },
+    validator::{is_relative_price, OrderValidator},
     Client, Result,
 };
+use chrono::SecondsFormat;
+use rust_decimal::Decimal;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-
-/// - <https://www.kraken.com/features/api#add-standard-order>
-/// - <https://api.kraken.com/0/private/AddOrder>
-/// - <https://support.kraken.com/hc/en-us/articles/205893708-Minimum-order-size-volume-for-trading>
-#[must_use = "Does nothing until you send or execute it"]
-pub struct AddOrderRequest {
-    client: Client,
-    pair: String,
+use serde_json::{Map, Value};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// The parameters of a single order, shared by `AddOrderRequest` and the
+/// orders of an `AddOrderBatchRequest`.
+#[derive(Debug, Clone)]
+pub struct OrderParams {
     order_side: OrderSide,
     order_type: OrderType,
     price: Option<String>,
@@ -22,29 +28,64 @@ pub struct AddOrderRequest {
     displayvol: Option<String>,
     // Amount of leverage desired.
     leverage: Option<String>,
-    /// Comma delimited list of order flags:
-    /// - fcib = prefer fee in base currency
-    /// - fciq = prefer fee in quote currency
-    /// - nompp = no market price protection
-    /// - post = post only order (available when ordertype = limit)
-    oflags: Option<String>,
+    /// Order flags, sent as a comma delimited list.
+    oflags: OrderFlags,
     /// Time-in-force of the order to specify how long it should remain in the
     /// order book before being cancelled.
-    timeinforce: Option<String>,
+    timeinforce: Option<TimeInForce>,
+    /// Price signal used to trigger stop and take-profit orders.
+    trigger: Option<Trigger>,
+    /// If true, order will only reduce a currently open position, not
+    /// increase it or open a new position.
+    reduce_only: Option<bool>,
+    /// Self trade prevention behavior.
+    stptype: Option<SelfTradePrevention>,
+    /// Client order id, mutually exclusive with `userref`.
+    cl_ord_id: Option<String>,
     /// Scheduled start time.
     starttm: Option<String>,
     /// Expiration time.
     expiretm: Option<String>,
     /// User reference id.
     userref: Option<i32>,
-    /// Validate inputs only, do not submit order.
-    validate: Option<bool>,
     close_order_type: Option<OrderType>,
     close_price: Option<String>,
     close_price2: Option<String>,
 }
 
-impl AddOrderRequest {
+impl OrderParams {
+    pub fn new(order_side: OrderSide, order_type: OrderType, volume: &str) -> Self {
+        Self {
+            order_side,
+            order_type,
+            price: None,
+            price2: None,
+            volume: volume.to_string(),
+            displayvol: None,
+            leverage: None,
+            oflags: OrderFlags::new(),
+            timeinforce: None,
+            trigger: None,
+            reduce_only: None,
+            stptype: None,
+            cl_ord_id: None,
+            starttm: None,
+            expiretm: None,
+            userref: None,
+            close_order_type: None,
+            close_price: None,
+            close_price2: None,
+        }
+    }
+
+    pub fn market(order_side: OrderSide, volume: &str) -> Self {
+        Self::new(order_side, OrderType::Market, volume)
+    }
+
+    pub fn limit(order_side: OrderSide, volume: &str, price: &str) -> Self {
+        Self::new(order_side, OrderType::Limit, volume).price(price)
+    }
+
     pub fn price(self, price: &str) -> Self {
         Self {
             price: Some(price.into()),
@@ -66,29 +107,32 @@ impl AddOrderRequest {
         }
     }
 
-    // TODO: add typed flags builder.
+    pub fn leverage(self, leverage: &str) -> Self {
+        Self {
+            leverage: Some(leverage.into()),
+            ..self
+        }
+    }
 
-    /// oflags = comma delimited list of order flags:
-    /// fcib = prefer fee in base currency
-    /// fciq = prefer fee in quote currency
-    /// nompp = no market price protection
-    /// post = post only order (available when ordertype = limit)
-    pub fn flags(self, flags: &str) -> Self {
+    /// Replaces the order flags, e.g. `OrderFlags::from([OrderFlag::Post,
+    /// OrderFlag::Fciq])` or `"post,fciq".parse()?`.
+    pub fn flags(self, flags: impl Into<OrderFlags>) -> Self {
         Self {
-            oflags: Some(flags.to_string()),
+            oflags: flags.into(),
             ..self
         }
     }
 
+    /// Adds an order flag, keeping the flags already set.
+    pub fn flag(mut self, flag: OrderFlag) -> Self {
+        self.oflags.insert(flag);
+        self
+    }
+
     /// A post only order prohibits a limit order to get immediately filled
     /// 'at market' and incur (potentially increased) market-order fees.
     pub fn post_only(self) -> Self {
-        // TODO: check that this is a limit order.
-        // TODO: don't override existing flags.
-        Self {
-            oflags: Some("post".to_string()),
-            ..self
-        }
+        self.flag(OrderFlag::Post)
     }
 
     /// Conditionally set `post_only`
@@ -100,16 +144,46 @@ impl AddOrderRequest {
         }
     }
 
-    // #todo introduce a timeinforce enum!
     /// Time-in-force of the order to specify how long it should remain in the
     /// order book before being cancelled. GTC (Good-'til-cancelled) is default
     /// if the parameter is omitted. IOC (immediate-or-cancel) will immediately
     /// execute the amount possible and cancel any remaining balance rather than
     /// resting in the book. GTD (good-'til-date), if specified, must coincide
     /// with a desired expiretm.
-    pub fn timeinforce(self, timeinforce: &str) -> Self {
+    pub fn timeinforce(self, timeinforce: TimeInForce) -> Self {
+        Self {
+            timeinforce: Some(timeinforce),
+            ..self
+        }
+    }
+
+    /// Price signal used to trigger stop-loss, take-profit and trailing-stop
+    /// orders.
+    pub fn trigger(self, trigger: Trigger) -> Self {
+        Self {
+            trigger: Some(trigger),
+            ..self
+        }
+    }
+
+    pub fn reduce_only(self, reduce_only: bool) -> Self {
         Self {
-            timeinforce: Some(timeinforce.to_owned()),
+            reduce_only: Some(reduce_only),
+            ..self
+        }
+    }
+
+    pub fn stptype(self, stptype: SelfTradePrevention) -> Self {
+        Self {
+            stptype: Some(stptype),
+            ..self
+        }
+    }
+
+    /// Client order id, an alternative to `userref`.
+    pub fn cl_ord_id(self, cl_ord_id: &str) -> Self {
+        Self {
+            cl_ord_id: Some(cl_ord_id.to_string()),
             ..self
         }
     }
@@ -119,11 +193,12 @@ impl AddOrderRequest {
     /// <n> = unix timestamp of expiration time
     pub fn starttm(self, starttm: &str) -> Self {
         Self {
-            starttm: Some(urlencoding::encode(starttm).to_string()),
+            starttm: Some(starttm.to_string()),
             ..self
         }
     }
 
+    /// Relative to the server clock, unaffected by local clock drift.
     pub fn start_after(self, seconds: u32) -> Self {
         self.starttm(&format!("+{}", seconds))
     }
@@ -137,11 +212,12 @@ impl AddOrderRequest {
     /// <n> = unix timestamp of expiration time
     pub fn expiretm(self, expiretm: &str) -> Self {
         Self {
-            expiretm: Some(urlencoding::encode(expiretm).to_string()),
+            expiretm: Some(expiretm.to_string()),
             ..self
         }
     }
 
+    /// Relative to the server clock, unaffected by local clock drift.
     pub fn expire_after(self, seconds: u32) -> Self {
         self.expiretm(&format!("+{}", seconds))
     }
@@ -175,24 +251,130 @@ impl AddOrderRequest {
         }
     }
 
-    pub fn validate(self, validate: bool) -> Self {
-        Self {
-            validate: Some(validate),
-            ..self
+    /// Rejects combinations of order parameters that the API does not accept,
+    /// without contacting the API.
+    pub fn check(&self) -> Result<()> {
+        let is_limit = matches!(self.order_type, OrderType::Limit | OrderType::Iceberg);
+
+        let is_triggered = matches!(
+            self.order_type,
+            OrderType::StopLoss
+                | OrderType::TakeProfit
+                | OrderType::StopLossLimit
+                | OrderType::TakeProfitLimit
+                | OrderType::TrailingStop
+                | OrderType::TrailingStopLimit
+        );
+
+        if self.oflags.contains(OrderFlag::Post) && !is_limit {
+            return Err(Error::invalid_order(format!(
+                "post only is not available for {} orders",
+                self.order_type
+            )));
+        }
+
+        if self.oflags.contains(OrderFlag::Post) && self.timeinforce == Some(TimeInForce::Ioc) {
+            return Err(Error::invalid_order(
+                "post only orders cannot be immediate-or-cancel",
+            ));
+        }
+
+        if self.oflags.contains(OrderFlag::Fcib) && self.oflags.contains(OrderFlag::Fciq) {
+            return Err(Error::invalid_order("fcib and fciq are mutually exclusive"));
+        }
+
+        if self.oflags.contains(OrderFlag::Viqc) && self.order_type != OrderType::Market {
+            return Err(Error::invalid_order(
+                "viqc is only available for market orders",
+            ));
+        }
+
+        if self.oflags.contains(OrderFlag::Nompp) && self.order_type != OrderType::Market {
+            return Err(Error::invalid_order(
+                "nompp is only available for market orders",
+            ));
+        }
+
+        if is_limit && self.price.is_none() {
+            return Err(Error::invalid_order(format!(
+                "{} orders require a price",
+                self.order_type
+            )));
         }
+
+        if self.order_type == OrderType::Market && self.price.is_some() {
+            return Err(Error::invalid_order("market orders do not accept a price"));
+        }
+
+        if self.timeinforce == Some(TimeInForce::Gtd) && self.expiretm.is_none() {
+            return Err(Error::invalid_order(
+                "good-'til-date orders require an expiration time",
+            ));
+        }
+
+        if self.trigger.is_some() && !is_triggered {
+            return Err(Error::invalid_order(format!(
+                "trigger is not available for {} orders",
+                self.order_type
+            )));
+        }
+
+        if self.userref.is_some() && self.cl_ord_id.is_some() {
+            return Err(Error::invalid_order(
+                "userref and cl_ord_id are mutually exclusive",
+            ));
+        }
+
+        Ok(())
     }
 
-    pub fn validate_only(self) -> Self {
-        Self {
-            validate: Some(true),
+    /// Like `check`, additionally checking the price and volume against the
+    /// metadata of `pair` cached in `validator`.
+    pub fn check_with(&self, pair: &str, validator: &OrderValidator) -> Result<()> {
+        self.check()?;
+
+        validator.check(pair, self.order_type, &self.volume, self.price.as_deref())
+    }
+
+    /// Rounds the prices to the nearest valid price level and truncates the
+    /// volume to the lot precision of `pair`. Relative prices are kept as-is,
+    /// other prices that are not decimals fail with `Error::InvalidOrder`.
+    pub fn quantize(self, pair: &str, validator: &OrderValidator) -> Result<Self> {
+        let round_price = |price: Option<String>| -> Result<Option<String>> {
+            match price {
+                Some(price) if is_relative_price(&price) => Ok(Some(price)),
+                Some(price) => {
+                    let value = Decimal::from_str(&price).map_err(|err| {
+                        Error::invalid_order(format!("invalid price `{price}`: {err}"))
+                    })?;
+
+                    Ok(Some(validator.round_price(pair, value)?.to_string()))
+                }
+                None => Ok(None),
+            }
+        };
+
+        let price = round_price(self.price.clone())?;
+        let price2 = round_price(self.price2.clone())?;
+
+        let volume = Decimal::from_str(&self.volume).map_err(|err| {
+            Error::invalid_order(format!("invalid volume `{}`: {err}", self.volume))
+        })?;
+        let volume = validator.round_volume(pair, volume)?.to_string();
+
+        Ok(Self {
+            price,
+            price2,
+            volume,
             ..self
-        }
+        })
     }
 
-    pub async fn execute<T: DeserializeOwned>(self) -> Result<T> {
+    /// Serializes the order as form parameters, as sent to `AddOrder`.
+    fn to_query(&self) -> String {
         let mut query = format!(
-            "pair={}&type={}&ordertype={}&volume={}",
-            self.pair, self.order_side, self.order_type, self.volume,
+            "type={}&ordertype={}&volume={}",
+            self.order_side, self.order_type, self.volume,
         );
 
         if let Some(price) = &self.price {
@@ -211,20 +393,36 @@ impl AddOrderRequest {
             query.push_str(&format!("&leverage={}", leverage));
         }
 
-        if let Some(oflags) = &self.oflags {
-            query.push_str(&format!("&oflags={}", oflags));
+        if !self.oflags.is_empty() {
+            query.push_str(&format!("&oflags={}", self.oflags));
         }
 
         if let Some(timeinforce) = &self.timeinforce {
             query.push_str(&format!("&timeinforce={}", timeinforce));
         }
 
+        if let Some(trigger) = &self.trigger {
+            query.push_str(&format!("&trigger={}", trigger));
+        }
+
+        if let Some(reduce_only) = &self.reduce_only {
+            query.push_str(&format!("&reduce_only={}", reduce_only));
+        }
+
+        if let Some(stptype) = &self.stptype {
+            query.push_str(&format!("&stptype={}", stptype));
+        }
+
+        if let Some(cl_ord_id) = &self.cl_ord_id {
+            query.push_str(&format!("&cl_ord_id={}", urlencoding::encode(cl_ord_id)));
+        }
+
         if let Some(starttm) = &self.starttm {
-            query.push_str(&format!("&starttm={}", starttm));
+            query.push_str(&format!("&starttm={}", urlencoding::encode(starttm)));
         }
 
         if let Some(expiretm) = &self.expiretm {
-            query.push_str(&format!("&expiretm={}", expiretm));
+            query.push_str(&format!("&expiretm={}", urlencoding::encode(expiretm)));
         }
 
         if let Some(close_order_type) = &self.close_order_type {
@@ -243,6 +441,250 @@ impl AddOrderRequest {
             query.push_str(&format!("&userref={}", userref));
         }
 
+        query
+    }
+
+    /// Serializes the order as a JSON object, as sent in the `orders` of
+    /// `AddOrderBatch`.
+    pub(crate) fn to_json(&self) -> Value {
+        let mut order = Map::new();
+
+        order.insert("type".into(), self.order_side.to_string().into());
+        order.insert("ordertype".into(), self.order_type.to_string().into());
+        order.insert("volume".into(), self.volume.clone().into());
+
+        let fields = [
+            ("price", &self.price),
+            ("price2", &self.price2),
+            ("displayvol", &self.displayvol),
+            ("leverage", &self.leverage),
+            ("starttm", &self.starttm),
+            ("expiretm", &self.expiretm),
+            ("cl_ord_id", &self.cl_ord_id),
+        ];
+
+        for (name, value) in fields {
+            if let Some(value) = value {
+                order.insert(name.into(), value.clone().into());
+            }
+        }
+
+        if !self.oflags.is_empty() {
+            order.insert("oflags".into(), self.oflags.to_string().into());
+        }
+
+        if let Some(timeinforce) = self.timeinforce {
+            order.insert("timeinforce".into(), timeinforce.to_string().into());
+        }
+
+        if let Some(trigger) = self.trigger {
+            order.insert("trigger".into(), trigger.to_string().into());
+        }
+
+        if let Some(reduce_only) = self.reduce_only {
+            order.insert("reduce_only".into(), reduce_only.into());
+        }
+
+        if let Some(stptype) = self.stptype {
+            order.insert("stptype".into(), stptype.to_string().into());
+        }
+
+        if let Some(userref) = self.userref {
+            order.insert("userref".into(), userref.into());
+        }
+
+        if let Some(close_order_type) = &self.close_order_type {
+            let mut close = Map::new();
+
+            close.insert("ordertype".into(), close_order_type.to_string().into());
+
+            if let Some(close_price) = &self.close_price {
+                close.insert("price".into(), close_price.clone().into());
+            }
+
+            if let Some(close_price2) = &self.close_price2 {
+                close.insert("price2".into(), close_price2.clone().into());
+            }
+
+            order.insert("close".into(), Value::Object(close));
+        }
+
+        Value::Object(order)
+    }
+}
+
+/// Add a standard order. The order setters forward to `OrderParams`.
+///
+/// - <https://www.kraken.com/features/api#add-standard-order>
+/// - <https://api.kraken.com/0/private/AddOrder>
+/// - <https://support.kraken.com/hc/en-us/articles/205893708-Minimum-order-size-volume-for-trading>
+#[must_use = "Does nothing until you send or execute it"]
+pub struct AddOrderRequest {
+    client: Client,
+    pair: String,
+    order: OrderParams,
+    /// RFC3339 timestamp after which the matching engine should reject the
+    /// new order request.
+    deadline: Option<String>,
+    /// Validate inputs only, do not submit order.
+    validate: Option<bool>,
+}
+
+impl AddOrderRequest {
+    fn map_order(self, f: impl FnOnce(OrderParams) -> OrderParams) -> Self {
+        Self {
+            order: f(self.order),
+            ..self
+        }
+    }
+
+    pub fn price(self, price: &str) -> Self {
+        self.map_order(|order| order.price(price))
+    }
+
+    pub fn price2(self, price: &str) -> Self {
+        self.map_order(|order| order.price2(price))
+    }
+
+    pub fn displayvol(self, displayvol: &str) -> Self {
+        self.map_order(|order| order.displayvol(displayvol))
+    }
+
+    pub fn leverage(self, leverage: &str) -> Self {
+        self.map_order(|order| order.leverage(leverage))
+    }
+
+    pub fn flags(self, flags: impl Into<OrderFlags>) -> Self {
+        self.map_order(|order| order.flags(flags))
+    }
+
+    pub fn flag(self, flag: OrderFlag) -> Self {
+        self.map_order(|order| order.flag(flag))
+    }
+
+    pub fn post_only(self) -> Self {
+        self.map_order(OrderParams::post_only)
+    }
+
+    pub fn post(self, post: bool) -> Self {
+        self.map_order(|order| order.post(post))
+    }
+
+    pub fn timeinforce(self, timeinforce: TimeInForce) -> Self {
+        self.map_order(|order| order.timeinforce(timeinforce))
+    }
+
+    pub fn trigger(self, trigger: Trigger) -> Self {
+        self.map_order(|order| order.trigger(trigger))
+    }
+
+    pub fn reduce_only(self, reduce_only: bool) -> Self {
+        self.map_order(|order| order.reduce_only(reduce_only))
+    }
+
+    pub fn stptype(self, stptype: SelfTradePrevention) -> Self {
+        self.map_order(|order| order.stptype(stptype))
+    }
+
+    pub fn cl_ord_id(self, cl_ord_id: &str) -> Self {
+        self.map_order(|order| order.cl_ord_id(cl_ord_id))
+    }
+
+    /// RFC3339 timestamp (e.g. 2021-04-01T00:18:45Z) after which the matching
+    /// engine should reject the new order request.
+    pub fn deadline(self, deadline: &str) -> Self {
+        Self {
+            deadline: Some(urlencoding::encode(deadline).to_string()),
+            ..self
+        }
+    }
+
+    /// Sets the deadline to `timeout` from now, on the server clock if it was
+    /// measured with `Client::sync_clock`. The deadline is computed when this
+    /// is called.
+    pub fn deadline_after(self, timeout: Duration) -> Self {
+        let deadline =
+            (self.client.server_now() + timeout).to_rfc3339_opts(SecondsFormat::Millis, true);
+
+        self.deadline(&deadline)
+    }
+
+    pub fn starttm(self, starttm: &str) -> Self {
+        self.map_order(|order| order.starttm(starttm))
+    }
+
+    pub fn start_after(self, seconds: u32) -> Self {
+        self.map_order(|order| order.start_after(seconds))
+    }
+
+    pub fn expiretm(self, expiretm: &str) -> Self {
+        self.map_order(|order| order.expiretm(expiretm))
+    }
+
+    pub fn expire_after(self, seconds: u32) -> Self {
+        self.map_order(|order| order.expire_after(seconds))
+    }
+
+    pub fn userref(self, userref: i32) -> Self {
+        self.map_order(|order| order.userref(userref))
+    }
+
+    pub fn close_order(
+        self,
+        close_order_type: OrderType,
+        close_price: Option<String>,
+        close_price2: Option<String>,
+    ) -> Self {
+        self.map_order(|order| order.close_order(close_order_type, close_price, close_price2))
+    }
+
+    pub fn close_limit_order(self, close_price: &str) -> Self {
+        self.map_order(|order| order.close_limit_order(close_price))
+    }
+
+    pub fn validate(self, validate: bool) -> Self {
+        Self {
+            validate: Some(validate),
+            ..self
+        }
+    }
+
+    pub fn validate_only(self) -> Self {
+        Self {
+            validate: Some(true),
+            ..self
+        }
+    }
+
+    /// Checks the order for incompatible parameters, without contacting the
+    /// API. Called by `execute`.
+    pub fn check(&self) -> Result<()> {
+        self.order.check()
+    }
+
+    /// Like `check`, additionally checking the price and volume against the
+    /// pair metadata cached in `validator`.
+    pub fn check_with(&self, validator: &OrderValidator) -> Result<()> {
+        self.order.check_with(&self.pair, validator)
+    }
+
+    /// Rounds the prices to the nearest valid price level and truncates the
+    /// volume to the lot precision of the pair, see `OrderParams::quantize`.
+    pub fn quantize(self, validator: &OrderValidator) -> Result<Self> {
+        let order = self.order.quantize(&self.pair, validator)?;
+
+        Ok(Self { order, ..self })
+    }
+
+    pub async fn execute<T: DeserializeOwned>(self) -> Result<T> {
+        self.check()?;
+
+        let mut query = format!("pair={}&{}", self.pair, self.order.to_query());
+
+        if let Some(deadline) = &self.deadline {
+            query.push_str(&format!("&deadline={}", deadline));
+        }
+
         if let Some(true) = &self.validate {
             query.push_str("&validate=true");
         }
@@ -255,6 +697,11 @@ impl AddOrderRequest {
     pub async fn send(self) -> Result<AddOrderResponse> {
         self.execute().await
     }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<AddOrderResponse> {
+        crate::blocking::block_on(self.send())
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -273,6 +720,16 @@ pub struct AddOrderResponse {
 }
 
 impl Client {
+    fn add_order_params(&self, pair: &str, order: OrderParams) -> AddOrderRequest {
+        AddOrderRequest {
+            client: self.clone(),
+            pair: pair.to_string(),
+            order,
+            deadline: None,
+            validate: None,
+        }
+    }
+
     pub fn add_order(
         &self,
         pair: &str,
@@ -280,26 +737,7 @@ impl Client {
         order_type: OrderType,
         volume: &str,
     ) -> AddOrderRequest {
-        AddOrderRequest {
-            client: self.clone(),
-            pair: pair.to_string(),
-            order_side,
-            order_type,
-            price: None,
-            price2: None,
-            displayvol: None,
-            volume: volume.to_string(),
-            leverage: None,
-            oflags: None,
-            timeinforce: None,
-            starttm: None,
-            expiretm: None,
-            userref: None,
-            validate: None,
-            close_order_type: None,
-            close_price: None,
-            close_price2: None,
-        }
+        self.add_order_params(pair, OrderParams::new(order_side, order_type, volume))
     }
 
     pub fn add_market_order(
@@ -308,26 +746,7 @@ impl Client {
         order_side: OrderSide,
         volume: &str,
     ) -> AddOrderRequest {
-        AddOrderRequest {
-            client: self.clone(),
-            pair: pair.to_string(),
-            order_side,
-            order_type: OrderType::Market,
-            price: None,
-            price2: None,
-            displayvol: None,
-            volume: volume.to_string(),
-            leverage: None,
-            oflags: None,
-            timeinforce: None,
-            starttm: None,
-            expiretm: None,
-            userref: None,
-            validate: None,
-            close_order_type: None,
-            close_price: None,
-            close_price2: None,
-        }
+        self.add_order_params(pair, OrderParams::market(order_side, volume))
     }
 
     pub fn add_limit_order(
@@ -337,26 +756,7 @@ impl Client {
         volume: &str,
         price: &str,
     ) -> AddOrderRequest {
-        AddOrderRequest {
-            client: self.clone(),
-            pair: pair.to_string(),
-            order_side,
-            order_type: OrderType::Limit,
-            price: Some(price.to_string()),
-            price2: None,
-            displayvol: None,
-            volume: volume.to_string(),
-            leverage: None,
-            oflags: None,
-            timeinforce: None,
-            starttm: None,
-            expiretm: None,
-            userref: None,
-            validate: None,
-            close_order_type: None,
-            close_price: None,
-            close_price2: None,
-        }
+        self.add_order_params(pair, OrderParams::limit(order_side, volume, price))
     }
 
     pub fn add_iceberg_order(
@@ -367,32 +767,20 @@ impl Client {
         volume: &str,
         price: &str,
     ) -> AddOrderRequest {
-        AddOrderRequest {
-            client: self.clone(),
-            pair: pair.to_string(),
-            order_side,
-            order_type: OrderType::Limit,
-            price: Some(price.to_string()),
-            price2: None,
-            displayvol: Some(displayvol.to_string()),
-            volume: volume.to_string(),
-            leverage: None,
-            oflags: None,
-            timeinforce: None,
-            starttm: None,
-            expiretm: None,
-            userref: None,
-            validate: None,
-            close_order_type: None,
-            close_price: None,
-            close_price2: None,
-        }
+        self.add_order_params(
+            pair,
+            OrderParams::limit(order_side, volume, price).displayvol(displayvol),
+        )
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{Client, OrderSide};
+    use super::OrderParams;
+    use crate::{
+        validator::OrderValidator, Client, Error, OrderFlag, OrderSide, OrderType, TimeInForce,
+        Trigger,
+    };
 
     #[tokio::test]
     async fn test_post_only() {
@@ -403,6 +791,144 @@ mod tests {
             .validate_only()
             .post_only();
 
-        assert_eq!(req.oflags, Some("post".to_string()));
+        assert_eq!(req.order.oflags.to_string(), "post");
+        assert!(matches!(req.check(), Err(Error::InvalidOrder(_))));
+    }
+
+    #[test]
+    fn post_only_keeps_existing_flags() {
+        let client = Client::default();
+
+        let req = client
+            .add_limit_order("XXBTZUSD", OrderSide::Buy, "0.1", "30000")
+            .flag(OrderFlag::Fciq)
+            .post_only();
+
+        assert_eq!(req.order.oflags.to_string(), "post,fciq");
+        assert!(req.check().is_ok());
+    }
+
+    #[test]
+    fn quantize_with_validator() {
+        let client = Client::default();
+
+        let pairs = serde_json::from_value(serde_json::json!({
+            "XXBTZUSD": {
+                "altname": "XBTUSD",
+                "aclass_base": "currency",
+                "base": "XXBT",
+                "aclass_quote": "currency",
+                "quote": "ZUSD",
+                "lot": "unit",
+                "pair_decimals": 1,
+                "lot_decimals": 8,
+                "lot_multiplier": 1,
+                "leverage_buy": [],
+                "leverage_sell": [],
+                "fees": [],
+                "fee_volume_currency": "ZUSD",
+                "margin_call": 80,
+                "margin_stop": 40,
+                "ordermin": "0.0001",
+                "tick_size": "0.1"
+            }
+        }))
+        .unwrap();
+
+        let validator = OrderValidator::from_pairs(pairs);
+
+        let req = client.add_limit_order("XBTUSD", OrderSide::Buy, "0.123456789", "30000.04");
+
+        assert!(req.check_with(&validator).is_err());
+
+        let req = req.quantize(&validator).unwrap();
+
+        assert_eq!(req.order.price.as_deref(), Some("30000"));
+        assert_eq!(req.order.volume, "0.12345678");
+        assert!(req.check_with(&validator).is_ok());
+    }
+
+    #[test]
+    fn quantize_keeps_relative_prices() {
+        let client = Client::default();
+
+        let validator = OrderValidator::from_pairs(
+            serde_json::from_value(serde_json::json!({
+                "XXBTZUSD": {
+                    "altname": "XBTUSD",
+                    "aclass_base": "currency",
+                    "base": "XXBT",
+                    "aclass_quote": "currency",
+                    "quote": "ZUSD",
+                    "lot": "unit",
+                    "pair_decimals": 1,
+                    "lot_decimals": 8,
+                    "lot_multiplier": 1,
+                    "leverage_buy": [],
+                    "leverage_sell": [],
+                    "fees": [],
+                    "fee_volume_currency": "ZUSD",
+                    "margin_call": 80,
+                    "margin_stop": 40,
+                    "ordermin": "0.0001",
+                    "tick_size": "0.1"
+                }
+            }))
+            .unwrap(),
+        );
+
+        let req = client
+            .add_order("XBTUSD", OrderSide::Buy, OrderType::StopLossLimit, "0.1")
+            .price("+1.5")
+            .price2("-2%")
+            .quantize(&validator)
+            .unwrap();
+
+        assert_eq!(req.order.price.as_deref(), Some("+1.5"));
+        assert_eq!(req.order.price2.as_deref(), Some("-2%"));
+
+        let result = client
+            .add_limit_order("XBTUSD", OrderSide::Buy, "0.1", "30k")
+            .quantize(&validator);
+
+        assert!(matches!(result, Err(Error::InvalidOrder(_))));
+    }
+
+    #[test]
+    fn serialize_order_params() {
+        let order = OrderParams::limit(OrderSide::Sell, "0.1", "30000")
+            .expire_after(60)
+            .cl_ord_id("my order");
+
+        assert_eq!(
+            order.to_query(),
+            "type=sell&ordertype=limit&volume=0.1&price=30000&cl_ord_id=my%20order&expiretm=%2B60"
+        );
+        assert_eq!(order.to_json()["expiretm"], "+60");
+        assert_eq!(order.to_json()["cl_ord_id"], "my order");
+    }
+
+    #[test]
+    fn reject_incompatible_options() {
+        let client = Client::default();
+
+        let req = client
+            .add_limit_order("XXBTZUSD", OrderSide::Buy, "0.1", "30000")
+            .timeinforce(TimeInForce::Gtd);
+
+        assert!(req.check().is_err());
+
+        let req = client
+            .add_limit_order("XXBTZUSD", OrderSide::Buy, "0.1", "30000")
+            .trigger(Trigger::Index);
+
+        assert!(req.check().is_err());
+
+        let req = client
+            .add_limit_order("XXBTZUSD", OrderSide::Buy, "0.1", "30000")
+            .userref(1)
+            .cl_ord_id("my-order");
+
+        assert!(req.check().is_err());
     }
 }
@@ -0,0 +1,216 @@
+use crate::{
+    api::{OrderDescription, OrderParams},
+    error::Error,
+    Client, Result,
+};
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::{Map, Value};
+
+/// The minimum number of orders in a batch.
+pub const MIN_BATCH_ORDERS: usize = 2;
+
+/// The maximum number of orders in a batch.
+pub const MAX_BATCH_ORDERS: usize = 15;
+
+/// Sends a collection of orders (minimum of 2 and maximum 15) for a single
+/// pair. Validation is performed on the whole batch prior to submission to
+/// the engine. If an order fails validation, the whole batch will be
+/// rejected.
+///
+/// - <https://docs.kraken.com/rest/#tag/Spot-Trading/operation/addOrderBatch>
+/// - <https://api.kraken.com/0/private/AddOrderBatch>
+#[must_use = "Does nothing until you send or execute it"]
+pub struct AddOrderBatchRequest {
+    client: Client,
+    pair: String,
+    orders: Vec<OrderParams>,
+    /// RFC3339 timestamp after which the matching engine should reject the
+    /// new orders
+    deadline: Option<String>,
+    /// Validate inputs only, do not submit orders.
+    validate: Option<bool>,
+}
+
+impl AddOrderBatchRequest {
+    /// Appends an order to the batch.
+    pub fn order(mut self, order: OrderParams) -> Self {
+        self.orders.push(order);
+        self
+    }
+
+    pub fn deadline(self, deadline: &str) -> Self {
+        Self {
+            deadline: Some(deadline.to_string()),
+            ..self
+        }
+    }
+
+    pub fn validate(self, validate: bool) -> Self {
+        Self {
+            validate: Some(validate),
+            ..self
+        }
+    }
+
+    pub fn validate_only(self) -> Self {
+        Self {
+            validate: Some(true),
+            ..self
+        }
+    }
+
+    pub async fn execute<T: DeserializeOwned>(self) -> Result<T> {
+        if !(MIN_BATCH_ORDERS..=MAX_BATCH_ORDERS).contains(&self.orders.len()) {
+            return Err(Error::invalid_order(format!(
+                "a batch must contain between {MIN_BATCH_ORDERS} and {MAX_BATCH_ORDERS} orders, got {}",
+                self.orders.len()
+            )));
+        }
+
+        for order in &self.orders {
+            order.check()?;
+        }
+
+        let mut params = Map::new();
+
+        params.insert("pair".into(), self.pair.into());
+        params.insert(
+            "orders".into(),
+            self.orders.iter().map(OrderParams::to_json).collect(),
+        );
+
+        if let Some(deadline) = self.deadline {
+            params.insert("deadline".into(), deadline.into());
+        }
+
+        if let Some(validate) = self.validate {
+            params.insert("validate".into(), validate.into());
+        }
+
+        self.client
+            .send_private_json("/0/private/AddOrderBatch", Value::Object(params))
+            .await
+    }
+
+    pub async fn send(self) -> Result<AddOrderBatchResponse> {
+        self.execute().await
+    }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<AddOrderBatchResponse> {
+        crate::blocking::block_on(self.send())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BatchOrderResult {
+    pub descr: Option<OrderDescription>,
+    /// Transaction id (if order was added successfully)
+    pub txid: Option<String>,
+    /// Error description from individual order processing
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AddOrderBatchResponse {
+    /// The results of the orders, in the order of the request
+    pub orders: Vec<BatchOrderResult>,
+}
+
+impl AddOrderBatchResponse {
+    /// Returns the txid of each order, or its error, in the order of the
+    /// request. Orders sent with `validate_only` have no txid.
+    pub fn results(self) -> Vec<Result<Option<String>>> {
+        self.orders
+            .into_iter()
+            .map(|order| match order.error {
+                Some(error) if !error.is_empty() => Err(Error::Api(error)),
+                _ => Ok(order.txid),
+            })
+            .collect()
+    }
+}
+
+impl Client {
+    pub fn add_order_batch(
+        &self,
+        pair: &str,
+        orders: impl Into<Vec<OrderParams>>,
+    ) -> AddOrderBatchRequest {
+        AddOrderBatchRequest {
+            client: self.clone(),
+            pair: pair.to_string(),
+            orders: orders.into(),
+            deadline: None,
+            validate: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AddOrderBatchResponse;
+    use crate::{api::OrderParams, Client, Error, OrderSide, OrderType};
+    use serde_json::json;
+
+    #[test]
+    fn order_params_to_json() {
+        let order = OrderParams::limit(OrderSide::Buy, "1.2", "27500.0")
+            .post_only()
+            .userref(42)
+            .close_order(OrderType::Limit, Some("28500.0".into()), None);
+
+        assert_eq!(
+            order.to_json(),
+            json!({
+                "type": "buy",
+                "ordertype": "limit",
+                "volume": "1.2",
+                "price": "27500.0",
+                "oflags": "post",
+                "userref": 42,
+                "close": {
+                    "ordertype": "limit",
+                    "price": "28500.0",
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn batch_results_in_order() {
+        let json = r#"{
+            "orders": [
+                {
+                    "txid": "OUF4EM-FRGI2-MQMWZD",
+                    "descr": { "order": "buy 1.25000000 XBTUSD @ limit 27500.0" }
+                },
+                {
+                    "error": "EOrder:Insufficient funds"
+                }
+            ]
+        }"#;
+
+        let resp: AddOrderBatchResponse = serde_json::from_str(json).unwrap();
+        let results = resp.results();
+
+        assert_eq!(
+            results[0].as_ref().unwrap().as_deref(),
+            Some("OUF4EM-FRGI2-MQMWZD")
+        );
+        assert!(
+            matches!(&results[1], Err(Error::Api(error)) if error == "EOrder:Insufficient funds")
+        );
+    }
+
+    #[tokio::test]
+    async fn batch_size_is_validated() {
+        let order = OrderParams::new(OrderSide::Buy, OrderType::Market, "1.0");
+        let result = Client::default()
+            .add_order_batch("XBTUSD", vec![order])
+            .send()
+            .await;
+
+        assert!(matches!(result, Err(Error::InvalidOrder(_))));
+    }
+}
@@ -0,0 +1,72 @@
+use crate::{Client, Result};
+use serde::de::DeserializeOwned;
+use serde_json::json;
+use std::time::Duration;
+
+/// Allocate funds to an earn strategy.
+///
+/// The operation is asynchronous; use `get_earn_allocation_status` or `send_and_wait` to
+/// track its completion.
+///
+/// - <https://docs.kraken.com/rest/#tag/Earn/operation/allocateStrategy>
+/// - <https://api.kraken.com/0/private/Earn/Allocate>
+#[must_use = "Does nothing until you send or execute it"]
+pub struct AllocateEarnFundsRequest {
+    client: Client,
+    /// The amount to allocate
+    amount: String,
+    /// A unique identifier of the chosen earn strategy, as returned from
+    /// `list_earn_strategies`
+    strategy_id: String,
+}
+
+impl AllocateEarnFundsRequest {
+    pub async fn execute<T: DeserializeOwned>(self) -> Result<T> {
+        let json = json!({
+            "amount": self.amount,
+            "strategy_id": self.strategy_id,
+        });
+
+        self.client
+            .send_private_json("/0/private/Earn/Allocate", json)
+            .await
+    }
+
+    /// Returns `true` if the request was accepted.
+    pub async fn send(self) -> Result<bool> {
+        self.execute().await
+    }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<bool> {
+        crate::blocking::block_on(self.send())
+    }
+
+    /// Sends the request and polls the allocation status every `poll_interval`
+    /// until the operation is no longer pending.
+    pub async fn send_and_wait(self, poll_interval: Duration) -> Result<()> {
+        let client = self.client.clone();
+        let strategy_id = self.strategy_id.clone();
+
+        self.send().await?;
+
+        client
+            .get_earn_allocation_status(strategy_id)
+            .wait(poll_interval)
+            .await
+    }
+}
+
+impl Client {
+    pub fn allocate_earn_funds(
+        &self,
+        strategy_id: impl Into<String>,
+        amount: impl Into<String>,
+    ) -> AllocateEarnFundsRequest {
+        AllocateEarnFundsRequest {
+            client: self.clone(),
+            amount: amount.into(),
+            strategy_id: strategy_id.into(),
+        }
+    }
+}
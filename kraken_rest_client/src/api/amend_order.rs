@@ -0,0 +1,159 @@
+use crate::{Client, Result};
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::{Map, Value};
+
+/// Modifies the parameters of a live order in-place, keeping its identifiers
+/// and, where possible, its queue priority in the order book.
+///
+/// - <https://docs.kraken.com/rest/#tag/Spot-Trading/operation/amendOrder>
+/// - <https://api.kraken.com/0/private/AmendOrder>
+#[must_use = "Does nothing until you send or execute it"]
+pub struct AmendOrderRequest {
+    client: Client,
+    /// The Kraken identifier of the order to amend
+    txid: Option<String>,
+    /// The client identifier of the order to amend
+    cl_ord_id: Option<String>,
+    /// The new order quantity in terms of the base asset
+    order_qty: Option<String>,
+    /// For iceberg orders only, the new quantity to show in the book
+    display_qty: Option<String>,
+    /// The new limit price restriction on the order
+    limit_price: Option<String>,
+    /// The new trigger price to activate the order
+    trigger_price: Option<String>,
+    /// Reject the amend if the new limit price would take liquidity
+    post_only: Option<bool>,
+    /// RFC3339 timestamp after which the matching engine should reject the
+    /// amend request
+    deadline: Option<String>,
+}
+
+impl AmendOrderRequest {
+    pub fn order_qty(self, order_qty: &str) -> Self {
+        Self {
+            order_qty: Some(order_qty.into()),
+            ..self
+        }
+    }
+
+    pub fn display_qty(self, display_qty: &str) -> Self {
+        Self {
+            display_qty: Some(display_qty.into()),
+            ..self
+        }
+    }
+
+    pub fn limit_price(self, limit_price: &str) -> Self {
+        Self {
+            limit_price: Some(limit_price.into()),
+            ..self
+        }
+    }
+
+    pub fn trigger_price(self, trigger_price: &str) -> Self {
+        Self {
+            trigger_price: Some(trigger_price.into()),
+            ..self
+        }
+    }
+
+    pub fn post_only(self, post_only: bool) -> Self {
+        Self {
+            post_only: Some(post_only),
+            ..self
+        }
+    }
+
+    pub fn deadline(self, deadline: &str) -> Self {
+        Self {
+            deadline: Some(deadline.into()),
+            ..self
+        }
+    }
+
+    pub async fn execute<T: DeserializeOwned>(self) -> Result<T> {
+        let mut params = Map::new();
+
+        if let Some(txid) = self.txid {
+            params.insert("txid".into(), txid.into());
+        }
+
+        if let Some(cl_ord_id) = self.cl_ord_id {
+            params.insert("cl_ord_id".into(), cl_ord_id.into());
+        }
+
+        if let Some(order_qty) = self.order_qty {
+            params.insert("order_qty".into(), order_qty.into());
+        }
+
+        if let Some(display_qty) = self.display_qty {
+            params.insert("display_qty".into(), display_qty.into());
+        }
+
+        if let Some(limit_price) = self.limit_price {
+            params.insert("limit_price".into(), limit_price.into());
+        }
+
+        if let Some(trigger_price) = self.trigger_price {
+            params.insert("trigger_price".into(), trigger_price.into());
+        }
+
+        if let Some(post_only) = self.post_only {
+            params.insert("post_only".into(), post_only.into());
+        }
+
+        if let Some(deadline) = self.deadline {
+            params.insert("deadline".into(), deadline.into());
+        }
+
+        self.client
+            .send_private_json("/0/private/AmendOrder", Value::Object(params))
+            .await
+    }
+
+    pub async fn send(self) -> Result<AmendOrderResponse> {
+        self.execute().await
+    }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<AmendOrderResponse> {
+        crate::blocking::block_on(self.send())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AmendOrderResponse {
+    /// The unique Kraken identifier generated for this amend transaction
+    pub amend_id: String,
+}
+
+impl Client {
+    /// Amends the order with the given Kraken order id.
+    pub fn amend_order(&self, txid: &str) -> AmendOrderRequest {
+        self.new_amend_order_request(Some(txid.to_string()), None)
+    }
+
+    /// Amends the order with the given client order id.
+    pub fn amend_order_by_cl_ord_id(&self, cl_ord_id: &str) -> AmendOrderRequest {
+        self.new_amend_order_request(None, Some(cl_ord_id.to_string()))
+    }
+
+    fn new_amend_order_request(
+        &self,
+        txid: Option<String>,
+        cl_ord_id: Option<String>,
+    ) -> AmendOrderRequest {
+        AmendOrderRequest {
+            client: self.clone(),
+            txid,
+            cl_ord_id,
+            order_qty: None,
+            display_qty: None,
+            limit_price: None,
+            trigger_price: None,
+            post_only: None,
+            deadline: None,
+        }
+    }
+}
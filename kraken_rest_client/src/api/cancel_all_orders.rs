@@ -16,6 +16,11 @@ impl CancelAllOrdersRequest {
     pub async fn send(self) -> Result<CancelAllOrdersResponse> {
         self.execute().await
     }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<CancelAllOrdersResponse> {
+        crate::blocking::block_on(self.send())
+    }
 }
 
 #[derive(Debug, Deserialize)]
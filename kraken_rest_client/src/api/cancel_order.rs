@@ -22,6 +22,11 @@ impl CancelOrderRequest {
     pub async fn send(self) -> Result<CancelOrderResponse> {
         self.execute().await
     }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<CancelOrderResponse> {
+        crate::blocking::block_on(self.send())
+    }
 }
 
 #[derive(Debug, Deserialize)]
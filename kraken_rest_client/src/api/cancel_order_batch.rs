@@ -28,6 +28,11 @@ impl CancelOrderBatchRequest {
     pub async fn send(self) -> Result<CancelOrderBatchResponse> {
         self.execute().await
     }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<CancelOrderBatchResponse> {
+        crate::blocking::block_on(self.send())
+    }
 }
 
 #[derive(Debug, Deserialize)]
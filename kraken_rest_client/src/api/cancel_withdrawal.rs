@@ -0,0 +1,46 @@
+use crate::{Client, Result};
+use serde::de::DeserializeOwned;
+
+/// Cancel a recently requested withdrawal, if it has not already been
+/// successfully processed.
+///
+/// - <https://docs.kraken.com/rest/#tag/Funding/operation/cancelWithdrawal>
+/// - <https://api.kraken.com/0/private/WithdrawCancel>
+#[must_use = "Does nothing until you send or execute it"]
+pub struct CancelWithdrawalRequest {
+    client: Client,
+    /// Asset being withdrawn
+    asset: String,
+    /// Withdrawal reference ID
+    refid: String,
+}
+
+impl CancelWithdrawalRequest {
+    pub async fn execute<T: DeserializeOwned>(self) -> Result<T> {
+        let query = format!("asset={}&refid={}", self.asset, self.refid);
+
+        self.client
+            .send_private("/0/private/WithdrawCancel", Some(query))
+            .await
+    }
+
+    /// Returns `true` if the cancellation was accepted.
+    pub async fn send(self) -> Result<bool> {
+        self.execute().await
+    }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<bool> {
+        crate::blocking::block_on(self.send())
+    }
+}
+
+impl Client {
+    pub fn cancel_withdrawal(&self, asset: &str, refid: &str) -> CancelWithdrawalRequest {
+        CancelWithdrawalRequest {
+            client: self.clone(),
+            asset: asset.to_string(),
+            refid: refid.to_string(),
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use crate::{Client, Result};
+use serde::de::DeserializeOwned;
+
+/// Create a trading subaccount. Must be called with an API key of the master
+/// account, only available to institutional clients.
+///
+/// - <https://docs.kraken.com/rest/#tag/Subaccounts/operation/createSubaccount>
+/// - <https://api.kraken.com/0/private/CreateSubaccount>
+#[must_use = "Does nothing until you send or execute it"]
+pub struct CreateSubaccountRequest {
+    client: Client,
+    /// Username for the subaccount
+    username: String,
+    /// Email address for the subaccount
+    email: String,
+}
+
+impl CreateSubaccountRequest {
+    pub async fn execute<T: DeserializeOwned>(self) -> Result<T> {
+        let query = format!(
+            "username={}&email={}",
+            urlencoding::encode(&self.username),
+            urlencoding::encode(&self.email)
+        );
+
+        self.client
+            .send_private("/0/private/CreateSubaccount", Some(query))
+            .await
+    }
+
+    /// Returns `true` if the subaccount was created.
+    pub async fn send(self) -> Result<bool> {
+        self.execute().await
+    }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<bool> {
+        crate::blocking::block_on(self.send())
+    }
+}
+
+impl Client {
+    pub fn create_subaccount(
+        &self,
+        username: impl Into<String>,
+        email: impl Into<String>,
+    ) -> CreateSubaccountRequest {
+        CreateSubaccountRequest {
+            client: self.clone(),
+            username: username.into(),
+            email: email.into(),
+        }
+    }
+}
@@ -0,0 +1,231 @@
+use crate::{api::OrderDescription, util::optional_string_or_number, Client, Result};
+use serde::{de::DeserializeOwned, Deserialize};
+
+/// Sends a request to edit the order parameters of a live order. When an
+/// order has been successfully modified, the original order will be cancelled
+/// and a new order will be created with the adjusted parameters and a new
+/// txid. Use `amend_order` to modify an order in-place, keeping its queue
+/// priority.
+///
+/// - <https://docs.kraken.com/rest/#tag/Spot-Trading/operation/editOrder>
+/// - <https://api.kraken.com/0/private/EditOrder>
+#[must_use = "Does nothing until you send or execute it"]
+pub struct EditOrderRequest {
+    client: Client,
+    /// Original order ID or user reference ID
+    txid: String,
+    /// Asset pair
+    pair: String,
+    /// User reference id for the new order
+    userref: Option<i32>,
+    /// Order quantity in terms of the base asset
+    volume: Option<String>,
+    /// For iceberg orders only, the amount to display in the order book
+    displayvol: Option<String>,
+    price: Option<String>,
+    /// Secondary price.
+    price2: Option<String>,
+    /// Comma delimited list of order flags. Only `post` is supported, the
+    /// original order flags are not retained.
+    oflags: Option<String>,
+    /// RFC3339 timestamp after which the matching engine should reject the
+    /// new order request
+    deadline: Option<String>,
+    /// Used to interpret if client wants to receive pending replace, before
+    /// the order is completely replaced
+    cancel_response: Option<bool>,
+    /// Validate inputs only, do not submit order.
+    validate: Option<bool>,
+}
+
+impl EditOrderRequest {
+    pub fn userref(self, userref: i32) -> Self {
+        Self {
+            userref: Some(userref),
+            ..self
+        }
+    }
+
+    pub fn volume(self, volume: &str) -> Self {
+        Self {
+            volume: Some(volume.into()),
+            ..self
+        }
+    }
+
+    pub fn displayvol(self, displayvol: &str) -> Self {
+        Self {
+            displayvol: Some(displayvol.into()),
+            ..self
+        }
+    }
+
+    pub fn price(self, price: &str) -> Self {
+        Self {
+            price: Some(urlencoding::encode(price).to_string()),
+            ..self
+        }
+    }
+
+    pub fn price2(self, price: &str) -> Self {
+        Self {
+            price2: Some(urlencoding::encode(price).to_string()),
+            ..self
+        }
+    }
+
+    pub fn flags(self, flags: &str) -> Self {
+        Self {
+            oflags: Some(flags.to_string()),
+            ..self
+        }
+    }
+
+    pub fn deadline(self, deadline: &str) -> Self {
+        Self {
+            deadline: Some(urlencoding::encode(deadline).to_string()),
+            ..self
+        }
+    }
+
+    pub fn cancel_response(self, cancel_response: bool) -> Self {
+        Self {
+            cancel_response: Some(cancel_response),
+            ..self
+        }
+    }
+
+    pub fn validate_only(self) -> Self {
+        Self {
+            validate: Some(true),
+            ..self
+        }
+    }
+
+    pub async fn execute<T: DeserializeOwned>(self) -> Result<T> {
+        let mut query = format!("txid={}&pair={}", self.txid, self.pair);
+
+        if let Some(userref) = &self.userref {
+            query.push_str(&format!("&userref={}", userref));
+        }
+
+        if let Some(volume) = &self.volume {
+            query.push_str(&format!("&volume={}", volume));
+        }
+
+        if let Some(displayvol) = &self.displayvol {
+            query.push_str(&format!("&displayvol={}", displayvol));
+        }
+
+        if let Some(price) = &self.price {
+            query.push_str(&format!("&price={}", price));
+        }
+
+        if let Some(price2) = &self.price2 {
+            query.push_str(&format!("&price2={}", price2));
+        }
+
+        if let Some(oflags) = &self.oflags {
+            query.push_str(&format!("&oflags={}", oflags));
+        }
+
+        if let Some(deadline) = &self.deadline {
+            query.push_str(&format!("&deadline={}", deadline));
+        }
+
+        if let Some(cancel_response) = &self.cancel_response {
+            query.push_str(&format!("&cancel_response={}", cancel_response));
+        }
+
+        if let Some(true) = &self.validate {
+            query.push_str("&validate=true");
+        }
+
+        self.client
+            .send_private("/0/private/EditOrder", Some(query))
+            .await
+    }
+
+    pub async fn send(self) -> Result<EditOrderResponse> {
+        self.execute().await
+    }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<EditOrderResponse> {
+        crate::blocking::block_on(self.send())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EditOrderResponse {
+    pub descr: OrderDescription,
+    /// The new transaction ID
+    pub txid: Option<String>,
+    /// Userref of the new order, if passed with the request
+    #[serde(default, deserialize_with = "optional_string_or_number")]
+    pub newuserref: Option<String>,
+    /// Userref of the original order, if passed with the request
+    #[serde(default, deserialize_with = "optional_string_or_number")]
+    pub olduserref: Option<String>,
+    /// Number of orders cancelled (either 0 or 1)
+    pub orders_cancelled: Option<i32>,
+    /// Original transaction ID
+    pub originaltxid: Option<String>,
+    /// Status of the order: `ok` or `err`
+    pub status: Option<String>,
+    /// Updated volume
+    pub volume: Option<String>,
+    /// Updated price
+    pub price: Option<String>,
+    /// Updated price2
+    pub price2: Option<String>,
+    /// Error message if unsuccessful
+    pub error_message: Option<String>,
+}
+
+impl Client {
+    /// Edits the order with the given `txid` (or userref) on `pair`.
+    pub fn edit_order(&self, txid: &str, pair: &str) -> EditOrderRequest {
+        EditOrderRequest {
+            client: self.clone(),
+            txid: txid.to_string(),
+            pair: pair.to_string(),
+            userref: None,
+            volume: None,
+            displayvol: None,
+            price: None,
+            price2: None,
+            oflags: None,
+            deadline: None,
+            cancel_response: None,
+            validate: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EditOrderResponse;
+
+    #[test]
+    fn deserialize_edit_order() {
+        let json = r#"{
+            "status": "ok",
+            "txid": "OFVXHJ-KPQ3B-VS7ELA",
+            "originaltxid": "OHYO67-6LP66-HMQ437",
+            "volume": "0.00030000",
+            "price": "19500.0",
+            "price2": "32500.0",
+            "orders_cancelled": 1,
+            "descr": {
+                "order": "buy 0.00030000 XXBTZGBP @ limit 19500.0"
+            }
+        }"#;
+
+        let resp: EditOrderResponse = serde_json::from_str(json).unwrap();
+
+        assert_eq!(resp.txid.as_deref(), Some("OFVXHJ-KPQ3B-VS7ELA"));
+        assert_eq!(resp.orders_cancelled, Some(1));
+        assert_eq!(resp.descr.order, "buy 0.00030000 XXBTZGBP @ limit 19500.0");
+    }
+}
@@ -17,6 +17,11 @@ impl GetAccountBalanceRequest {
     pub async fn send(self) -> Result<GetAccountBalanceResponse> {
         self.execute().await
     }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<GetAccountBalanceResponse> {
+        crate::blocking::block_on(self.send())
+    }
 }
 
 pub type GetAccountBalanceResponse = HashMap<String, String>;
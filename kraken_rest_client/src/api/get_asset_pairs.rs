@@ -1,5 +1,5 @@
 use crate::{Client, Result};
-use serde::{de::DeserializeOwned, Deserialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// - <https://www.kraken.com/features/api#get-tradable-pairs>
@@ -32,6 +32,11 @@ impl GetAssetPairsRequest {
     pub async fn send(self) -> Result<GetAssetPairsResponse> {
         self.execute().await
     }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<GetAssetPairsResponse> {
+        crate::blocking::block_on(self.send())
+    }
 }
 
 // #todo consider making FeeSchedule a struct with named fields.
@@ -42,10 +47,10 @@ impl GetAssetPairsRequest {
 //   pub percent_fee: f64
 // }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct FeeSchedule(pub f64, pub f64);
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct PairInfo {
     /// alternate pair name
     pub altname: String,
@@ -83,6 +88,15 @@ pub struct PairInfo {
     pub margin_stop: f64,
     /// minimum order volume for pair
     pub ordermin: Option<String>,
+    /// minimum order cost (in quote currency)
+    pub costmin: Option<String>,
+    /// minimum increment between valid price levels
+    pub tick_size: Option<String>,
+    /// scaling decimal places for cost
+    pub cost_decimals: Option<i32>,
+    /// status of asset: online, cancel_only, post_only, limit_only,
+    /// reduce_only
+    pub status: Option<String>,
 }
 
 pub type GetAssetPairsResponse = HashMap<String, PairInfo>;
@@ -98,25 +112,32 @@ impl Client {
 
 #[cfg(test)]
 mod tests {
-    use crate::{Client, JsonValue, PairName, Result};
+    use crate::mock::MockKraken;
+    use crate::{JsonValue, PairName, Result};
 
     #[tokio::test]
     async fn get_asset_pairs() {
-        let client = Client::default();
+        let mock = MockKraken::start().await;
+        mock.serve("public/AssetPairs").await;
 
-        let resp = client.get_asset_pairs().pair("XXBTZUSD").send().await;
+        let client = mock.client();
 
-        match resp {
-            Ok(resp) => println!("{:?}", resp),
-            Err(error) => eprintln!("{:?}", error),
-        }
+        let resp = client
+            .get_asset_pairs()
+            .pair("XXBTZUSD")
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(resp["XXBTZUSD"].altname, "XBTUSD");
 
         let pair = PairName::from("BTC", "USD");
         let resp: Result<JsonValue> = client.get_asset_pairs().pair(&pair).execute().await;
 
-        match resp {
-            Ok(resp) => println!("{:?}", resp),
-            Err(error) => eprintln!("{:?}", error),
-        }
+        assert_eq!(resp.unwrap()["XXBTZUSD"]["quote"], "ZUSD");
+
+        let requests = mock.requests().await;
+
+        assert_eq!(requests[1].url.query(), Some("pair=XXBTZUSD"));
     }
 }
@@ -1,5 +1,5 @@
 use crate::{Client, Result};
-use serde::{de::DeserializeOwned, Deserialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// - <https://docs.kraken.com/rest/#operation/getAssetInfo>
@@ -33,9 +33,14 @@ impl GetAssetsRequest {
     pub async fn send(self) -> Result<GetAssetsResponse> {
         self.execute().await
     }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<GetAssetsResponse> {
+        crate::blocking::block_on(self.send())
+    }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Asset {
     pub altname: String,
     pub aclass: String,
@@ -56,25 +61,27 @@ impl Client {
 
 #[cfg(test)]
 mod tests {
-    use crate::{AssetName, Client};
+    use crate::mock::MockKraken;
+    use crate::AssetName;
 
     #[tokio::test]
     async fn get_assets() {
-        let client = Client::default();
+        let mock = MockKraken::start().await;
+        mock.serve("public/Assets").await;
 
-        let resp = client.get_assets().asset("DOT,XXRP,XXMR").send().await;
+        let client = mock.client();
 
-        match resp {
-            Ok(resp) => println!("{:?}", resp),
-            Err(error) => eprintln!("{:?}", error),
-        }
+        let resp = client.get_assets().asset("XXBT,ZUSD").send().await.unwrap();
 
-        let asset = AssetName::from("XRP");
-        let resp = client.get_assets().asset(&asset).send().await;
+        assert_eq!(resp["XXBT"].altname, "XBT");
+        assert_eq!(resp["ZUSD"].decimals, 4);
 
-        match resp {
-            Ok(resp) => println!("{:?}", resp),
-            Err(error) => eprintln!("{:?}", error),
-        }
+        let asset = AssetName::from("XBT");
+        client.get_assets().asset(&asset).send().await.unwrap();
+
+        let requests = mock.requests().await;
+
+        assert_eq!(requests[0].url.query(), Some("asset=XXBT,ZUSD"));
+        assert_eq!(requests[1].url.query(), Some("asset=XXBT"));
     }
 }
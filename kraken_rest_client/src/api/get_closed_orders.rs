@@ -1,12 +1,16 @@
-use crate::{Client, OrderDescription, Result};
+use crate::{
+    paginate::{paginate, Page, DEFAULT_PAGE_DELAY},
+    Client, OrderInfo, Result,
+};
+use futures::Stream;
 use serde::{de::DeserializeOwned, Deserialize};
-use std::collections::HashMap;
+use std::{collections::HashMap, time::Duration};
 
-// TODO: This endpoint is under construction. Don't use yet!
 // TODO: Rename to `get_close_orders_history`.
 
 /// - <https://docs.kraken.com/rest/#operation/getClosedOrders>
 /// - <https://api.kraken.com/0/private/ClosedOrders>
+#[derive(Clone)]
 #[must_use = "Does nothing until you send or execute it"]
 pub struct GetClosedOrdersRequest {
     client: Client,
@@ -99,33 +103,52 @@ impl GetClosedOrdersRequest {
     pub async fn send(self) -> Result<GetClosedOrdersResponse> {
         self.execute().await
     }
-}
 
-#[derive(Debug, Deserialize)]
-pub struct ClosedOrderInfo {
-    pub userref: Option<i32>,
-    pub status: String,
-    pub descr: OrderDescription,
-    pub oflags: String,
-    pub opentm: f64,
-    pub closetm: f64,
-    pub expiretm: f64,
-    pub vol: String,
-    pub vol_exec: String,
-    pub fee: String,
-    pub misc: String,
-    pub limitprice: String,
-    pub refid: Option<String>,
-    pub reason: Option<String>,
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<GetClosedOrdersResponse> {
+        crate::blocking::block_on(self.send())
+    }
+
+    /// Returns a stream of all closed orders matching the request, fetching the
+    /// result pages as needed.
+    pub fn stream(self) -> impl Stream<Item = Result<(String, ClosedOrderInfo)>> {
+        self.stream_with_page_delay(DEFAULT_PAGE_DELAY)
+    }
+
+    /// Like `stream`, with a custom pause between page requests.
+    pub fn stream_with_page_delay(
+        self,
+        page_delay: Duration,
+    ) -> impl Stream<Item = Result<(String, ClosedOrderInfo)>> {
+        paginate(move |ofs| self.clone().ofs(ofs).send(), page_delay)
+    }
 }
 
-// TODO: not fully implemented yet, use JsonValue instead!
+pub type ClosedOrderInfo = OrderInfo;
+
 #[derive(Debug, Deserialize)]
 pub struct GetClosedOrdersResponse {
     pub closed: HashMap<String, ClosedOrderInfo>,
     pub count: usize,
 }
 
+impl Page for GetClosedOrdersResponse {
+    type Item = ClosedOrderInfo;
+
+    fn count(&self) -> usize {
+        self.count
+    }
+
+    fn into_entries(self) -> Vec<(String, ClosedOrderInfo)> {
+        let mut entries: Vec<_> = self.closed.into_iter().collect();
+        entries.sort_by(|(_, a), (_, b)| {
+            let closetm = |order: &OrderInfo| order.closetm.unwrap_or_default();
+            closetm(b).total_cmp(&closetm(a))
+        });
+        entries
+    }
+}
+
 impl Client {
     pub fn get_closed_orders(&self) -> GetClosedOrdersRequest {
         GetClosedOrdersRequest {
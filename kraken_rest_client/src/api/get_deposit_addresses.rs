@@ -1,6 +1,9 @@
 use crate::{Client, Result};
 use serde::{de::DeserializeOwned, Deserialize};
 
+/// Retrieve (or generate a new) deposit addresses for a particular asset and
+/// method.
+///
 /// - <https://docs.kraken.com/rest/#tag/User-Funding/operation/getDepositAddresses>
 /// - <https://api.kraken.com/0/private/DepositAddresses>
 #[must_use = "Does nothing until you send or execute it"]
@@ -10,11 +13,43 @@ pub struct GetDepositAddressesRequest {
     asset: String,
     /// Name of the deposit method
     method: String,
+    /// Whether or not to generate a new address
+    new: Option<bool>,
+    /// Amount you wish to deposit (only required for `method=Bitcoin Lightning`)
+    amount: Option<String>,
 }
 
 impl GetDepositAddressesRequest {
+    /// Whether or not to generate a new address
+    pub fn generate_new(self, new: bool) -> Self {
+        Self {
+            new: Some(new),
+            ..self
+        }
+    }
+
+    /// Amount you wish to deposit (only required for `method=Bitcoin Lightning`)
+    pub fn amount(self, amount: impl Into<String>) -> Self {
+        Self {
+            amount: Some(amount.into()),
+            ..self
+        }
+    }
+
     pub async fn execute<T: DeserializeOwned>(self) -> Result<T> {
-        let query = format!("asset={}&method={}", self.asset, self.method);
+        let mut query = format!(
+            "asset={}&method={}",
+            self.asset,
+            urlencoding::encode(&self.method)
+        );
+
+        if let Some(new) = self.new {
+            query.push_str(&format!("&new={}", new));
+        }
+
+        if let Some(amount) = self.amount {
+            query.push_str(&format!("&amount={}", amount));
+        }
 
         self.client
             .send_private("/0/private/DepositAddresses", Some(query))
@@ -24,13 +59,25 @@ impl GetDepositAddressesRequest {
     pub async fn send(self) -> Result<Vec<DepositAddresses>> {
         self.execute().await
     }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<Vec<DepositAddresses>> {
+        crate::blocking::block_on(self.send())
+    }
 }
 
 #[derive(Debug, Deserialize)]
 pub struct DepositAddresses {
+    /// Deposit Address
     pub address: String,
+    /// Expiration time in unix timestamp, or 0 if not expiring
     pub expiretm: String,
+    /// Whether or not address has ever been used
     pub new: Option<bool>,
+    /// Memo, for assets that require one
+    pub memo: Option<String>,
+    /// Tag, for assets that require one
+    pub tag: Option<String>,
 }
 
 impl Client {
@@ -43,6 +90,30 @@ impl Client {
             client: self.clone(),
             asset: asset.into(),
             method: method.into(),
+            new: None,
+            amount: None,
         }
     }
+
+    /// Generates a new deposit address for the given asset and method.
+    pub fn new_deposit_address(
+        &self,
+        asset: impl Into<String>,
+        method: impl Into<String>,
+    ) -> GetDepositAddressesRequest {
+        self.get_deposit_addresses(asset, method).generate_new(true)
+    }
+
+    /// Requests a deposit address for a specific amount, e.g. a Lightning
+    /// invoice.
+    pub fn get_deposit_address_for_amount(
+        &self,
+        asset: impl Into<String>,
+        method: impl Into<String>,
+        amount: impl Into<String>,
+    ) -> GetDepositAddressesRequest {
+        self.get_deposit_addresses(asset, method)
+            .generate_new(true)
+            .amount(amount)
+    }
 }
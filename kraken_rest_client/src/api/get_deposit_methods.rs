@@ -22,6 +22,11 @@ impl GetDepositMethodsRequest {
     pub async fn send(self) -> Result<Vec<DepositMethods>> {
         self.execute().await
     }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<Vec<DepositMethods>> {
+        crate::blocking::block_on(self.send())
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -1,8 +1,20 @@
-use crate::{Client, Result};
+use crate::{
+    error::Error, paginate::retry_rate_limited, Client, FundingStatus, FundingStatusProp, Result,
+    DEFAULT_PAGE_DELAY,
+};
+use futures::{stream, Stream};
 use serde::{de::DeserializeOwned, Deserialize};
+use std::time::Duration;
 
+/// The number of polls `watch_deposit` waits for a deposit to show up in the
+/// status list.
+const MAX_MISSING_DEPOSIT_POLLS: usize = 5;
+
+/// Retrieve information about recent deposits.
+///
 /// - <https://docs.kraken.com/rest/#tag/User-Funding/operation/getStatusRecentDeposits>
 /// - <https://api.kraken.com/0/private/DepositStatus>
+#[derive(Clone)]
 #[must_use = "Does nothing until you send or execute it"]
 pub struct GetDepositStatusRequest {
     client: Client,
@@ -10,6 +22,16 @@ pub struct GetDepositStatusRequest {
     asset: String,
     /// Name of the deposit method
     method: Option<String>,
+    /// Start timestamp, deposits created strictly before will not be included
+    /// in the response
+    start: Option<i64>,
+    /// End timestamp, deposits created strictly after will be not be included
+    /// in the response
+    end: Option<i64>,
+    /// Cursor of the page to fetch
+    cursor: Option<String>,
+    /// Number of results to include per page
+    limit: Option<u32>,
 }
 
 impl GetDepositStatusRequest {
@@ -21,11 +43,57 @@ impl GetDepositStatusRequest {
         }
     }
 
+    pub fn start(self, start: i64) -> Self {
+        Self {
+            start: Some(start),
+            ..self
+        }
+    }
+
+    pub fn end(self, end: i64) -> Self {
+        Self {
+            end: Some(end),
+            ..self
+        }
+    }
+
+    /// The `next_cursor` of a previous page, see `send_page`.
+    pub fn cursor(self, cursor: impl Into<String>) -> Self {
+        Self {
+            cursor: Some(cursor.into()),
+            ..self
+        }
+    }
+
+    /// Number of results to include per page, see `send_page`.
+    pub fn limit(self, limit: u32) -> Self {
+        Self {
+            limit: Some(limit),
+            ..self
+        }
+    }
+
     pub async fn execute<T: DeserializeOwned>(self) -> Result<T> {
         let mut query = format!("asset={}", self.asset);
 
         if let Some(method) = self.method {
-            query.push_str(format!("&method={}", method).as_str());
+            query.push_str(format!("&method={}", urlencoding::encode(&method)).as_str());
+        }
+
+        if let Some(start) = self.start {
+            query.push_str(format!("&start={}", start).as_str());
+        }
+
+        if let Some(end) = self.end {
+            query.push_str(format!("&end={}", end).as_str());
+        }
+
+        if let Some(cursor) = self.cursor {
+            query.push_str(format!("&cursor={}", urlencoding::encode(&cursor)).as_str());
+        }
+
+        if let Some(limit) = self.limit {
+            query.push_str(format!("&limit={}", limit).as_str());
         }
 
         self.client
@@ -33,15 +101,78 @@ impl GetDepositStatusRequest {
             .await
     }
 
+    /// Returns the recent deposits as a single, unpaginated list.
     pub async fn send(self) -> Result<Vec<DepositStatus>> {
-        self.execute().await
+        Self {
+            cursor: None,
+            ..self
+        }
+        .execute()
+        .await
+    }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<Vec<DepositStatus>> {
+        crate::blocking::block_on(self.send())
+    }
+
+    /// Returns a single page of deposits. Starts from the first page unless a
+    /// `cursor` is set.
+    pub async fn send_page(self) -> Result<DepositStatusPage> {
+        let cursor = self.cursor.clone().unwrap_or_else(|| "true".to_string());
+
+        self.cursor(cursor).execute().await
+    }
+
+    /// Returns a stream of all deposits matching the request, following the
+    /// `next_cursor` of each page.
+    pub fn stream(self) -> impl Stream<Item = Result<DepositStatus>> {
+        self.stream_with_page_delay(DEFAULT_PAGE_DELAY)
+    }
+
+    /// Like `stream`, with a custom pause between page requests.
+    pub fn stream_with_page_delay(
+        self,
+        page_delay: Duration,
+    ) -> impl Stream<Item = Result<DepositStatus>> {
+        stream::unfold(
+            (Some(self), Vec::new().into_iter(), false),
+            move |(mut req, mut buffer, mut started)| async move {
+                loop {
+                    if let Some(deposit) = buffer.next() {
+                        return Some((Ok(deposit), (req, buffer, started)));
+                    }
+
+                    let current = req.take()?;
+
+                    if started && !page_delay.is_zero() {
+                        tokio::time::sleep(page_delay).await;
+                    }
+
+                    started = true;
+
+                    match retry_rate_limited(|| current.clone().send_page(), page_delay).await {
+                        Ok(page) => {
+                            req = page
+                                .next_cursor
+                                .filter(|_| !page.deposit.is_empty())
+                                .map(|cursor| current.cursor(cursor));
+                            buffer = page.deposit.into_iter();
+                        }
+                        Err(error) => return Some((Err(error), (None, buffer, started))),
+                    }
+                }
+            },
+        )
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct DepositStatus {
     /// Name of deposit method
     pub method: String,
+    /// Network name based on the funding method used
+    pub network: Option<String>,
     /// Asset class
     pub aclass: String,
     /// Asset
@@ -59,9 +190,20 @@ pub struct DepositStatus {
     /// Unix timestamp when request was made
     pub time: u64,
     /// Status of deposit
-    pub status: String,
+    pub status: FundingStatus,
+    /// Additional status properties (if available)
     #[serde(rename = "status-prop")]
-    pub status_prop: Option<String>,
+    pub status_prop: Option<FundingStatusProp>,
+    /// Client sending transaction id(s) for deposits that credit with a
+    /// sweeping transaction
+    pub originators: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DepositStatusPage {
+    pub deposit: Vec<DepositStatus>,
+    /// Cursor of the next page, absent means end of list
+    pub next_cursor: Option<String>,
 }
 
 impl Client {
@@ -70,6 +212,129 @@ impl Client {
             client: self.clone(),
             asset: asset.into(),
             method: None,
+            start: None,
+            end: None,
+            cursor: None,
+            limit: None,
         }
     }
+
+    /// Polls the status of the deposit with the given `refid` every
+    /// `poll_interval`, yielding the deposit each time its `status` or
+    /// `status_prop` changes. Rate limited polls are retried with backoff. The
+    /// stream ends once the deposit succeeds or fails, or after any other
+    /// error. Fails if the deposit is not listed in the recent deposits, e.g.
+    /// an unknown `refid` or one that dropped out of the listed window.
+    pub fn watch_deposit(
+        &self,
+        asset: impl Into<String>,
+        refid: impl Into<String>,
+        poll_interval: Duration,
+    ) -> impl Stream<Item = Result<DepositStatus>> {
+        let req = self.get_deposit_status(asset);
+        let refid = refid.into();
+
+        let state = Some((req, refid, None, false, 0));
+
+        stream::unfold(state, move |state| async move {
+            let (req, refid, mut last, mut started, mut missing_polls) = state?;
+
+            loop {
+                if started {
+                    tokio::time::sleep(poll_interval).await;
+                }
+
+                started = true;
+
+                let poll = retry_rate_limited(|| req.clone().send(), poll_interval);
+
+                let deposits = match poll.await {
+                    Ok(deposits) => deposits,
+                    Err(error) => return Some((Err(error), None)),
+                };
+
+                let Some(deposit) = deposits.into_iter().find(|d| d.refid == refid) else {
+                    // A new deposit may take a few polls to be listed.
+                    if last.is_none() && missing_polls + 1 < MAX_MISSING_DEPOSIT_POLLS {
+                        missing_polls += 1;
+                        continue;
+                    }
+
+                    let error = Error::internal(format!(
+                        "deposit `{refid}` is not listed in the recent deposits"
+                    ));
+
+                    return Some((Err(error), None));
+                };
+
+                let state = Some((deposit.status, deposit.status_prop));
+
+                if state == last {
+                    continue;
+                }
+
+                last = state;
+
+                let next = if deposit.status.is_final() {
+                    None
+                } else {
+                    Some((req, refid, last, started, missing_polls))
+                };
+
+                return Some((Ok(deposit), next));
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DepositStatusPage, MAX_MISSING_DEPOSIT_POLLS};
+    use crate::mock::MockKraken;
+    use crate::FundingStatus;
+    use futures::StreamExt;
+    use std::time::Duration;
+
+    #[test]
+    fn deserialize_deposit_status_page() {
+        let json = r#"{
+            "deposit": [
+                {
+                    "method": "Bitcoin",
+                    "aclass": "currency",
+                    "asset": "XXBT",
+                    "refid": "FTQcuak-V6Za8qrWnhzTx67yYHz8Tg",
+                    "txid": "6544b41b607d8b2512baf801755a3a87b6890eacdb451be8a94059fb11f0a8d9",
+                    "info": "2Myd4eaAW96ojk38A2uDK4FbioCayvkEgVq",
+                    "amount": "0.78125000",
+                    "fee": "0.0000000000",
+                    "time": 1688992722,
+                    "status": "Success",
+                    "status-prop": "return"
+                }
+            ],
+            "next_cursor": "ZW5kOjE2ODg5OTI3MjI"
+        }"#;
+
+        let page: DepositStatusPage = serde_json::from_str(json).unwrap();
+
+        assert_eq!(page.deposit[0].status, FundingStatus::Success);
+        assert_eq!(page.next_cursor.as_deref(), Some("ZW5kOjE2ODg5OTI3MjI"));
+    }
+
+    #[tokio::test]
+    async fn watch_deposit_fails_if_not_listed() {
+        let mock = MockKraken::start().await;
+        mock.serve("private/DepositStatus").await;
+
+        let updates: Vec<_> = mock
+            .client()
+            .watch_deposit("XBT", "FTQcuak-unknown", Duration::ZERO)
+            .collect()
+            .await;
+
+        assert_eq!(updates.len(), 1);
+        assert!(updates[0].is_err());
+        assert_eq!(mock.requests().await.len(), MAX_MISSING_DEPOSIT_POLLS);
+    }
 }
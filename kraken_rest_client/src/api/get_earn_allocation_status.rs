@@ -0,0 +1,66 @@
+use crate::{Client, Result};
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::json;
+use std::time::Duration;
+
+/// Get the status of the last allocation request.
+///
+/// - <https://docs.kraken.com/rest/#tag/Earn/operation/getAllocateStrategyStatus>
+/// - <https://api.kraken.com/0/private/Earn/AllocateStatus>
+#[derive(Clone)]
+#[must_use = "Does nothing until you send or execute it"]
+pub struct GetEarnAllocationStatusRequest {
+    client: Client,
+    /// ID of the earn strategy, call `list_earn_strategies` to list available
+    /// strategies
+    strategy_id: String,
+}
+
+impl GetEarnAllocationStatusRequest {
+    pub async fn execute<T: DeserializeOwned>(self) -> Result<T> {
+        let json = json!({
+            "strategy_id": self.strategy_id,
+        });
+
+        self.client
+            .send_private_json("/0/private/Earn/AllocateStatus", json)
+            .await
+    }
+
+    pub async fn send(self) -> Result<EarnOperationStatus> {
+        self.execute().await
+    }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<EarnOperationStatus> {
+        crate::blocking::block_on(self.send())
+    }
+
+    /// Polls the status every `poll_interval` until the operation is no
+    /// longer pending. Wrap in `tokio::time::timeout` to bound the wait.
+    pub async fn wait(self, poll_interval: Duration) -> Result<()> {
+        while self.clone().send().await?.pending {
+            tokio::time::sleep(poll_interval).await;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EarnOperationStatus {
+    /// `true` if an operation is still in progress on the same strategy
+    pub pending: bool,
+}
+
+impl Client {
+    pub fn get_earn_allocation_status(
+        &self,
+        strategy_id: impl Into<String>,
+    ) -> GetEarnAllocationStatusRequest {
+        GetEarnAllocationStatusRequest {
+            client: self.clone(),
+            strategy_id: strategy_id.into(),
+        }
+    }
+}
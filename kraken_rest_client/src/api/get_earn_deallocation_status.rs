@@ -0,0 +1,60 @@
+use crate::{api::EarnOperationStatus, Client, Result};
+use serde::de::DeserializeOwned;
+use serde_json::json;
+use std::time::Duration;
+
+/// Get the status of the last deallocation request.
+///
+/// - <https://docs.kraken.com/rest/#tag/Earn/operation/getDeallocateStrategyStatus>
+/// - <https://api.kraken.com/0/private/Earn/DeallocateStatus>
+#[derive(Clone)]
+#[must_use = "Does nothing until you send or execute it"]
+pub struct GetEarnDeallocationStatusRequest {
+    client: Client,
+    /// ID of the earn strategy, call `list_earn_strategies` to list available
+    /// strategies
+    strategy_id: String,
+}
+
+impl GetEarnDeallocationStatusRequest {
+    pub async fn execute<T: DeserializeOwned>(self) -> Result<T> {
+        let json = json!({
+            "strategy_id": self.strategy_id,
+        });
+
+        self.client
+            .send_private_json("/0/private/Earn/DeallocateStatus", json)
+            .await
+    }
+
+    pub async fn send(self) -> Result<EarnOperationStatus> {
+        self.execute().await
+    }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<EarnOperationStatus> {
+        crate::blocking::block_on(self.send())
+    }
+
+    /// Polls the status every `poll_interval` until the operation is no
+    /// longer pending. Wrap in `tokio::time::timeout` to bound the wait.
+    pub async fn wait(self, poll_interval: Duration) -> Result<()> {
+        while self.clone().send().await?.pending {
+            tokio::time::sleep(poll_interval).await;
+        }
+
+        Ok(())
+    }
+}
+
+impl Client {
+    pub fn get_earn_deallocation_status(
+        &self,
+        strategy_id: impl Into<String>,
+    ) -> GetEarnDeallocationStatusRequest {
+        GetEarnDeallocationStatusRequest {
+            client: self.clone(),
+            strategy_id: strategy_id.into(),
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use crate::{api::ExportReport, error::Error, Client, Result};
+use serde::{de::DeserializeOwned, Deserialize};
+use std::time::Duration;
+
+/// Get status of requested data exports.
+///
+/// - <https://docs.kraken.com/rest/#tag/Account-Data/operation/exportStatus>
+/// - <https://api.kraken.com/0/private/ExportStatus>
+#[must_use = "Does nothing until you send or execute it"]
+pub struct GetExportStatusRequest {
+    client: Client,
+    /// Type of reports to inquire about
+    report: ExportReport,
+}
+
+impl GetExportStatusRequest {
+    pub async fn execute<T: DeserializeOwned>(self) -> Result<T> {
+        let query = format!("report={}", self.report);
+
+        self.client
+            .send_private("/0/private/ExportStatus", Some(query))
+            .await
+    }
+
+    pub async fn send(self) -> Result<GetExportStatusResponse> {
+        self.execute().await
+    }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<GetExportStatusResponse> {
+        crate::blocking::block_on(self.send())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum ExportState {
+    Queued,
+    Processing,
+    Processed,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExportStatus {
+    /// Report ID
+    pub id: String,
+    pub descr: String,
+    pub format: String,
+    pub report: String,
+    pub subtype: Option<String>,
+    /// Status of the report
+    pub status: ExportState,
+    pub flags: Option<String>,
+    pub fields: String,
+    /// UNIX timestamp of report request
+    pub createdtm: String,
+    pub expiretm: Option<String>,
+    /// UNIX timestamp report processing began
+    pub starttm: String,
+    /// UNIX timestamp report processing finished
+    pub completedtm: String,
+    /// UNIX timestamp of the report data start time
+    pub datastarttm: String,
+    /// UNIX timestamp of the report data end time
+    pub dataendtm: String,
+    pub aclass: Option<String>,
+    pub asset: String,
+}
+
+pub type GetExportStatusResponse = Vec<ExportStatus>;
+
+impl Client {
+    pub fn get_export_status(&self, report: ExportReport) -> GetExportStatusRequest {
+        GetExportStatusRequest {
+            client: self.clone(),
+            report,
+        }
+    }
+
+    /// Polls the status of the export with the given `id` every
+    /// `poll_interval` until it is processed. Fails if the export leaves the
+    /// queue without being processed. Wrap in `tokio::time::timeout` to bound
+    /// the wait.
+    pub async fn wait_for_export(
+        &self,
+        report: ExportReport,
+        id: &str,
+        poll_interval: Duration,
+    ) -> Result<ExportStatus> {
+        loop {
+            let status = self
+                .get_export_status(report)
+                .send()
+                .await?
+                .into_iter()
+                .find(|status| status.id == id)
+                .ok_or_else(|| Error::internal(format!("export `{id}` not found")))?;
+
+            match status.status {
+                ExportState::Processed => return Ok(status),
+                ExportState::Queued | ExportState::Processing => {
+                    tokio::time::sleep(poll_interval).await;
+                }
+                ExportState::Unknown => {
+                    return Err(Error::internal(format!(
+                        "export `{id}` will not be processed, its status is unknown"
+                    )));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::ExportReport;
+    use crate::mock::{fixture, MockKraken};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn wait_for_export_fails_on_unexpected_states() {
+        let mock = MockKraken::start().await;
+        let body = fixture("private/ExportStatus").replace("Processed", "Failed");
+        mock.serve_body("private/ExportStatus", body, "application/json")
+            .await;
+
+        let result = mock
+            .client()
+            .wait_for_export(ExportReport::Trades, "VSKC", Duration::ZERO)
+            .await;
+
+        assert!(result.is_err());
+    }
+}
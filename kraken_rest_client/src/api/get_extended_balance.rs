@@ -0,0 +1,137 @@
+use crate::{api::GetTradeBalanceResponse, error::Error, Client, Result};
+use rust_decimal::Decimal;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// Retrieve all extended account balances, including credits and held
+/// amounts.
+///
+/// - <https://docs.kraken.com/rest/#tag/Account-Data/operation/getExtendedBalance>
+/// - <https://api.kraken.com/0/private/BalanceEx>
+#[must_use = "Does nothing until you send or execute it"]
+pub struct GetExtendedBalanceRequest {
+    client: Client,
+}
+
+impl GetExtendedBalanceRequest {
+    pub async fn execute<T: DeserializeOwned>(self) -> Result<T> {
+        self.client.send_private("/0/private/BalanceEx", None).await
+    }
+
+    pub async fn send(self) -> Result<GetExtendedBalanceResponse> {
+        self.execute().await
+    }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<GetExtendedBalanceResponse> {
+        crate::blocking::block_on(self.send())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExtendedBalance {
+    /// Total balance amount for the asset
+    pub balance: String,
+    /// Total credit amount, only if the account has a credit line
+    pub credit: Option<String>,
+    /// Used credit amount
+    pub credit_used: Option<String>,
+    /// Total amount held in open orders
+    #[serde(default)]
+    pub hold_trade: String,
+}
+
+impl ExtendedBalance {
+    /// The amount available to trade: `balance + credit - credit_used -
+    /// hold_trade`.
+    pub fn available(&self) -> Result<Decimal> {
+        let balance = parse_amount(&self.balance)?;
+        let credit = parse_amount(self.credit.as_deref().unwrap_or_default())?;
+        let credit_used = parse_amount(self.credit_used.as_deref().unwrap_or_default())?;
+        let hold_trade = parse_amount(&self.hold_trade)?;
+
+        Ok(balance + credit - credit_used - hold_trade)
+    }
+}
+
+/// Parses an amount, empty amounts are zero.
+fn parse_amount(amount: &str) -> Result<Decimal> {
+    if amount.is_empty() {
+        return Ok(Decimal::ZERO);
+    }
+
+    Decimal::from_str(amount)
+        .map_err(|err| Error::internal(format!("invalid amount `{amount}`: {err}")))
+}
+
+pub type GetExtendedBalanceResponse = HashMap<String, ExtendedBalance>;
+
+/// The balances of an account together with its margin info, for pre-trade
+/// checks.
+#[derive(Debug)]
+pub struct AccountFunds {
+    pub balances: GetExtendedBalanceResponse,
+    pub trade_balance: GetTradeBalanceResponse,
+}
+
+impl AccountFunds {
+    /// The amount of `asset` available to trade, i.e. not held in open
+    /// orders. Zero if the account holds none.
+    pub fn available(&self, asset: &str) -> Result<Decimal> {
+        match self.balances.get(asset) {
+            Some(balance) => balance.available(),
+            None => Ok(Decimal::ZERO),
+        }
+    }
+
+    /// The margin available to open new positions, in the trade balance
+    /// asset.
+    pub fn free_margin(&self) -> Result<Decimal> {
+        parse_amount(&self.trade_balance.free_margin)
+    }
+}
+
+impl Client {
+    pub fn get_extended_balance(&self) -> GetExtendedBalanceRequest {
+        GetExtendedBalanceRequest {
+            client: self.clone(),
+        }
+    }
+
+    /// Retrieves the extended balances and the trade balance (in ZUSD) of the
+    /// account.
+    pub async fn get_account_funds(&self) -> Result<AccountFunds> {
+        // Sent one after the other, concurrent requests may arrive with
+        // out-of-order nonces.
+        let balances = self.get_extended_balance().send().await?;
+        let trade_balance = self.get_trade_balance().send().await?;
+
+        Ok(AccountFunds {
+            balances,
+            trade_balance,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mock::MockKraken;
+    use rust_decimal::Decimal;
+
+    #[tokio::test]
+    async fn get_account_funds() {
+        let mock = MockKraken::start().await;
+        mock.serve("private/BalanceEx").await;
+        mock.serve("private/TradeBalance").await;
+
+        let funds = mock.client().get_account_funds().await.unwrap();
+
+        assert_eq!(funds.balances["XXBT"].credit, None);
+        assert_eq!(funds.available("ZUSD").unwrap(), Decimal::new(1718545, 2));
+        assert_eq!(funds.available("XXBT").unwrap(), Decimal::new(4012, 4));
+        assert_eq!(funds.available("XETH").unwrap(), Decimal::new(15000, 4));
+        assert_eq!(funds.available("DOT").unwrap(), Decimal::ZERO);
+        assert_eq!(funds.free_margin().unwrap(), Decimal::new(3751678, 4));
+    }
+}
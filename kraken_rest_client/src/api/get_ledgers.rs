@@ -1,9 +1,14 @@
-use crate::{Client, Result};
+use crate::{
+    paginate::{paginate, Page, DEFAULT_PAGE_DELAY},
+    Client, Result,
+};
+use futures::Stream;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use std::collections::HashMap;
+use std::{collections::HashMap, time::Duration};
 
 /// - <https://docs.kraken.com/rest/#tag/User-Data/operation/getLedgers>
 /// - <https://api.kraken.com/0/private/Ledgers>
+#[derive(Clone)]
 #[must_use = "Does nothing until you send or execute it"]
 pub struct GetLedgersRequest {
     client: Client,
@@ -104,6 +109,25 @@ impl GetLedgersRequest {
     pub async fn send(self) -> Result<GetLedgersResponse> {
         self.execute().await
     }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<GetLedgersResponse> {
+        crate::blocking::block_on(self.send())
+    }
+
+    /// Returns a stream of all ledger entries matching the request, fetching the
+    /// result pages as needed.
+    pub fn stream(self) -> impl Stream<Item = Result<(String, LedgerEntry)>> {
+        self.stream_with_page_delay(DEFAULT_PAGE_DELAY)
+    }
+
+    /// Like `stream`, with a custom pause between page requests.
+    pub fn stream_with_page_delay(
+        self,
+        page_delay: Duration,
+    ) -> impl Stream<Item = Result<(String, LedgerEntry)>> {
+        paginate(move |ofs| self.clone().ofs(ofs as u32).send(), page_delay)
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -190,6 +214,20 @@ pub struct GetLedgersResponse {
     pub count: u32,
 }
 
+impl Page for GetLedgersResponse {
+    type Item = LedgerEntry;
+
+    fn count(&self) -> usize {
+        self.count as usize
+    }
+
+    fn into_entries(self) -> Vec<(String, LedgerEntry)> {
+        let mut entries: Vec<_> = self.ledger.into_iter().collect();
+        entries.sort_by(|(_, a), (_, b)| b.time.total_cmp(&a.time));
+        entries
+    }
+}
+
 impl Client {
     pub fn get_ledgers(&self) -> GetLedgersRequest {
         GetLedgersRequest {
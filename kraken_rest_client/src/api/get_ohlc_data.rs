@@ -1,8 +1,9 @@
-use crate::{error::Error, Client, JsonValue, Result};
+use crate::{error::Error, raw::decode_value, Client, JsonValue, Result};
 use serde::{de::DeserializeOwned, Deserialize};
 use std::collections::HashMap;
 
 /// Time frame interval.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Interval {
     Min1 = 1,
     Min5 = 5,
@@ -15,6 +16,13 @@ pub enum Interval {
     Day15 = 21_600,
 }
 
+impl Interval {
+    /// The length of the interval in seconds.
+    pub fn seconds(self) -> i64 {
+        self as i64 * 60
+    }
+}
+
 /// - <https://docs.kraken.com/rest/#operation/getOHLCData>
 /// - <https://api.kraken.com/0/public/OHLC>
 #[must_use = "Does nothing until you send or execute it"]
@@ -61,15 +69,16 @@ impl GetOhlcDataRequest {
         let resp = self.execute::<GetOhlcDataRawResponse>().await?;
 
         if let Some(value) = resp.get(&pair) {
-            if let Ok(ohlc_data) = serde_json::from_value(value.clone()) {
-                Ok(ohlc_data)
-            } else {
-                Err(Error::internal("cannot deserialize OHLC data"))
-            }
+            decode_value(value)
         } else {
             Err(Error::internal("no OHLC data"))
         }
     }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<GetOhlcDataResponse> {
+        crate::blocking::block_on(self.send())
+    }
 }
 
 // TODO: better name?
@@ -143,41 +152,48 @@ impl Client {
 
 #[cfg(test)]
 mod tests {
-    use crate::{Client, Interval, PairName};
+    use crate::mock::MockKraken;
+    use crate::{Interval, PairName};
 
     #[tokio::test]
     async fn get_ohlc_data() {
-        let client = Client::default();
+        let mock = MockKraken::start().await;
+        mock.serve("public/OHLC").await;
 
         let pair = PairName::from("XBT", "USD");
-        let ohlc_bars = client
+        let ohlc_bars = mock
+            .client()
             .get_ohlc_data(&pair)
             .interval(Interval::Day1)
             .send()
-            .await;
-
-        // dbg!(&ohlc_bars);
+            .await
+            .unwrap();
 
-        if let Ok(ohlc_bars) = ohlc_bars {
-            assert!(!ohlc_bars.is_empty());
-        }
+        assert_eq!(ohlc_bars.len(), 2);
+        assert_eq!(ohlc_bars[1].open(), "30304.5");
+        assert_eq!(ohlc_bars[1].count(), 18);
     }
 
     #[tokio::test]
     async fn get_ohlc_data_with_since_param() {
-        let client = Client::default();
+        let mock = MockKraken::start().await;
+        mock.serve("public/OHLC").await;
 
-        let pair = PairName::from("XBT", "USD");
-        let ohlc_bars = client
-            .get_ohlc_data(&pair)
+        let ohlc_bars = mock
+            .client()
+            .get_ohlc_data("XXBTZUSD")
             .since(1548111600) //Kraken Example: since=154811160
             .send()
-            .await;
+            .await
+            .unwrap();
 
-        // dbg!(&ohlc_bars);
+        assert_eq!(ohlc_bars[0].time(), 1688671200);
 
-        if let Ok(ohlc_bars) = ohlc_bars {
-            assert!(!ohlc_bars.is_empty());
-        }
+        let requests = mock.requests().await;
+
+        assert_eq!(
+            requests[0].url.query(),
+            Some("pair=XXBTZUSD&since=1548111600")
+        );
     }
 }
@@ -1,4 +1,4 @@
-use crate::{Client, OrderDescription, Result};
+use crate::{Client, OrderInfo, Result};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -53,24 +53,18 @@ impl GetOpenOrdersRequest {
     pub async fn send(self) -> Result<GetOpenOrdersResponse> {
         self.execute().await
     }
-}
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct OpenOrderInfo {
-    pub status: String,
-    pub cost: String,
-    pub descr: OrderDescription,
-    pub opentm: f64,
-    pub oflags: String,
-    pub fee: String,
-    pub vol: String,
-    pub vol_executed: Option<String>,
-    pub userref: Option<i32>,
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<GetOpenOrdersResponse> {
+        crate::blocking::block_on(self.send())
+    }
 }
 
+pub type OpenOrderInfo = OrderInfo;
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct GetOpenOrdersResponse {
-    pub open: HashMap<String, OpenOrderInfo>,
+    pub open: HashMap<String, OrderInfo>,
 }
 
 impl Client {
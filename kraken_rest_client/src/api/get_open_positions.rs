@@ -37,6 +37,11 @@ impl GetOpenPositionsRequest {
     pub async fn send(self) -> Result<GetOpenPositionsResponse> {
         self.execute().await
     }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<GetOpenPositionsResponse> {
+        crate::blocking::block_on(self.send())
+    }
 }
 
 #[derive(Debug, Deserialize)]
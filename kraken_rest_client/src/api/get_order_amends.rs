@@ -0,0 +1,129 @@
+use crate::{Client, Result};
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::json;
+
+/// Retrieves an audit trail of amend transactions on the specified order. The
+/// list is ordered by ascending amend timestamp.
+///
+/// - <https://docs.kraken.com/rest/#tag/Spot-Trading/operation/getOrderAmends>
+/// - <https://api.kraken.com/0/private/OrderAmends>
+#[must_use = "Does nothing until you send or execute it"]
+pub struct GetOrderAmendsRequest {
+    client: Client,
+    /// The Kraken order identifier
+    order_id: String,
+}
+
+impl GetOrderAmendsRequest {
+    pub async fn execute<T: DeserializeOwned>(self) -> Result<T> {
+        let json = json!({
+            "order_id": self.order_id,
+        });
+
+        self.client
+            .send_private_json("/0/private/OrderAmends", json)
+            .await
+    }
+
+    pub async fn send(self) -> Result<GetOrderAmendsResponse> {
+        self.execute().await
+    }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<GetOrderAmendsResponse> {
+        crate::blocking::block_on(self.send())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AmendType {
+    /// The initial order
+    Original,
+    /// An amend requested by the user
+    User,
+    /// An amend made by the exchange, e.g. to restrict the price of a
+    /// post-only order
+    Restated,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OrderAmend {
+    /// Kraken amend identifier
+    pub amend_id: String,
+    /// The type of amend transaction
+    pub amend_type: AmendType,
+    /// Order quantity in terms of the base asset
+    pub order_qty: String,
+    /// For iceberg orders only, the quantity to show in the book
+    pub display_qty: Option<String>,
+    /// Remaining unfilled quantity in terms of the base asset
+    pub remaining_qty: String,
+    /// The limit price restriction on the order
+    pub limit_price: Option<String>,
+    /// The trigger price on trigger order types
+    pub trigger_price: Option<String>,
+    /// Description of the reason for this amend
+    pub reason: Option<String>,
+    /// Indicates if the order has been restricted from taking liquidity
+    pub post_only: bool,
+    /// The UNIX timestamp for the amend transaction, in milliseconds
+    pub timestamp: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GetOrderAmendsResponse {
+    /// The total count of new and amend transactions (i.e. includes the
+    /// original order)
+    pub count: u32,
+    pub amends: Vec<OrderAmend>,
+}
+
+impl Client {
+    pub fn get_order_amends(&self, order_id: &str) -> GetOrderAmendsRequest {
+        GetOrderAmendsRequest {
+            client: self.clone(),
+            order_id: order_id.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AmendType, GetOrderAmendsResponse};
+
+    #[test]
+    fn deserialize_order_amends() {
+        let json = r#"{
+            "count": 2,
+            "amends": [
+                {
+                    "amend_id": "TNGJFU-5CD67-ZV3AEO",
+                    "amend_type": "original",
+                    "order_qty": "1.25000000",
+                    "remaining_qty": "1.25000000",
+                    "limit_price": "28000.0",
+                    "post_only": false,
+                    "timestamp": 1699362300119
+                },
+                {
+                    "amend_id": "TNJOJ5-ROX4Z-ALHUN3",
+                    "amend_type": "user",
+                    "order_qty": "1.00000000",
+                    "remaining_qty": "1.00000000",
+                    "limit_price": "28100.0",
+                    "post_only": false,
+                    "timestamp": 1699362348245
+                }
+            ]
+        }"#;
+
+        let resp: GetOrderAmendsResponse = serde_json::from_str(json).unwrap();
+
+        assert_eq!(resp.count, 2);
+        assert_eq!(resp.amends[0].amend_type, AmendType::Original);
+        assert_eq!(resp.amends[1].limit_price.as_deref(), Some("28100.0"));
+    }
+}
@@ -34,6 +34,11 @@ impl GetOrderBookRequest {
     pub async fn send(self) -> Result<GetOrderBookResponse> {
         self.execute().await
     }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<GetOrderBookResponse> {
+        crate::blocking::block_on(self.send())
+    }
 }
 #[derive(Debug, Serialize, Deserialize)]
 pub struct OrderBookTier(pub String, pub String, pub i32);
@@ -60,17 +65,25 @@ impl Client {
 
 #[cfg(test)]
 mod tests {
-    use crate::Client;
+    use crate::mock::MockKraken;
 
     #[tokio::test]
     async fn get_order_book() {
-        let client = Client::default();
+        let mock = MockKraken::start().await;
+        mock.serve("public/Depth").await;
 
-        let resp = client.get_order_book("XXBTZUSD").count(5).send().await;
+        let resp = mock
+            .client()
+            .get_order_book("XXBTZUSD")
+            .count(5)
+            .send()
+            .await
+            .unwrap();
 
-        match resp {
-            Ok(resp) => println!("{:?}", resp),
-            Err(error) => eprintln!("{:?}", error),
-        }
+        assert_eq!(resp["XXBTZUSD"].asks[0].0, "30384.10000");
+
+        let requests = mock.requests().await;
+
+        assert_eq!(requests[0].url.query(), Some("pair=XXBTZUSD&count=5"));
     }
 }
@@ -0,0 +1,174 @@
+use crate::{backfill::DEFAULT_BACKFILL_DELAY, paginate::retry_rate_limited, Client, Result};
+use futures::{stream, Stream};
+use serde::{de::DeserializeOwned, Deserialize};
+use std::{collections::HashMap, time::Duration};
+
+/// Returns the last ~200 top-of-book spreads for a given pair.
+///
+/// - <https://docs.kraken.com/rest/#tag/Market-Data/operation/getRecentSpreads>
+/// - <https://api.kraken.com/0/public/Spread>
+#[derive(Clone)]
+#[must_use = "Does nothing until you send or execute it"]
+pub struct GetRecentSpreadsRequest {
+    client: Client,
+    pair: String,
+    since: Option<i64>,
+}
+
+impl GetRecentSpreadsRequest {
+    /// Returns spread data since given timestamp. Optional, intended for
+    /// incremental updates within available dataset (does not contain all
+    /// historical spreads).
+    pub fn since(self, since: i64) -> Self {
+        Self {
+            since: Some(since),
+            ..self
+        }
+    }
+
+    pub async fn execute<T: DeserializeOwned>(self) -> Result<T> {
+        let mut url = format!("/0/public/Spread?pair={}", self.pair);
+
+        if let Some(since) = self.since {
+            url.push_str(&format!("&since={since}"))
+        }
+
+        self.client.send_public(&url).await
+    }
+
+    pub async fn send(self) -> Result<GetRecentSpreadsResponse> {
+        self.execute().await
+    }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<GetRecentSpreadsResponse> {
+        crate::blocking::block_on(self.send())
+    }
+
+    /// Returns a stream of spreads that follows the `last` cursor until no new
+    /// spreads are returned.
+    pub fn stream(self) -> impl Stream<Item = Result<Spread>> {
+        self.stream_with_delay(DEFAULT_BACKFILL_DELAY)
+    }
+
+    /// Like `stream`, with a custom pause between requests.
+    pub fn stream_with_delay(self, delay: Duration) -> impl Stream<Item = Result<Spread>> {
+        stream::unfold(
+            (self, Vec::new().into_iter(), false, false),
+            move |(req, mut buffer, started, done)| async move {
+                if let Some(spread) = buffer.next() {
+                    return Some((Ok(spread), (req, buffer, started, done)));
+                }
+
+                if done {
+                    return None;
+                }
+
+                if started && !delay.is_zero() {
+                    tokio::time::sleep(delay).await;
+                }
+
+                match retry_rate_limited(|| req.clone().send(), delay).await {
+                    Ok(resp) => {
+                        let spreads = resp.pair.into_values().next().unwrap_or_default();
+                        let done = spreads.is_empty() || Some(resp.last) == req.since;
+                        let req = req.since(resp.last);
+                        let mut buffer = spreads.into_iter();
+
+                        buffer
+                            .next()
+                            .map(|spread| (Ok(spread), (req, buffer, true, done)))
+                    }
+                    Err(error) => Some((Err(error), (req, buffer, true, true))),
+                }
+            },
+        )
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Spread(
+    /// time (0)
+    pub i64,
+    /// bid (1)
+    pub String,
+    /// ask (2)
+    pub String,
+);
+
+impl Spread {
+    pub fn time(&self) -> i64 {
+        self.0
+    }
+
+    pub fn bid(&self) -> &str {
+        &self.1
+    }
+
+    pub fn ask(&self) -> &str {
+        &self.2
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct GetRecentSpreadsResponse {
+    /// ID to be used as `since` when polling for new spread data
+    pub last: i64,
+
+    #[serde(flatten)]
+    pub pair: HashMap<String, Vec<Spread>>,
+}
+
+impl Client {
+    pub fn get_recent_spreads(&self, pair: impl Into<String>) -> GetRecentSpreadsRequest {
+        GetRecentSpreadsRequest {
+            client: self.clone(),
+            pair: pair.into(),
+            since: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GetRecentSpreadsResponse;
+    use crate::mock::MockKraken;
+
+    #[tokio::test]
+    async fn get_recent_spreads() {
+        let mock = MockKraken::start().await;
+        mock.serve("public/Spread").await;
+
+        let spreads = mock
+            .client()
+            .get_recent_spreads("XXBTZUSD")
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(spreads.last, 1688672106);
+        assert!(!spreads.pair["XXBTZUSD"].is_empty());
+    }
+
+    #[test]
+    fn deserialize_recent_spreads() {
+        let json = r#"{
+            "XXBTZUSD": [
+                [1688671834, "30292.10000", "30297.50000"],
+                [1688671834, "30296.70000", "30297.50000"]
+            ],
+            "last": 1688672106
+        }"#;
+
+        let resp: GetRecentSpreadsResponse = serde_json::from_str(json).unwrap();
+
+        assert_eq!(resp.last, 1688672106);
+
+        let spreads = &resp.pair["XXBTZUSD"];
+
+        assert_eq!(spreads.len(), 2);
+        assert_eq!(spreads[0].time(), 1688671834);
+        assert_eq!(spreads[0].bid(), "30292.10000");
+        assert_eq!(spreads[1].ask(), "30297.50000");
+    }
+}
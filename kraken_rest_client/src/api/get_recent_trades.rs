@@ -44,6 +44,11 @@ impl GetRecentTradesRequest {
     pub async fn send(self) -> Result<GetRecentTradesResponse> {
         self.execute::<GetRecentTradesResponse>().await
     }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<GetRecentTradesResponse> {
+        crate::blocking::block_on(self.send())
+    }
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -115,21 +120,30 @@ impl Client {
 
 #[cfg(test)]
 mod tests {
-    use crate::Client;
+    use crate::mock::MockKraken;
 
     #[tokio::test]
     async fn get_recent_trades() {
-        let client = Client::default();
+        let mock = MockKraken::start().await;
+        mock.serve("public/Trades").await;
 
-        let trades = client
-            .get_recent_trades("BTC/USD")
+        let trades = mock
+            .client()
+            .get_recent_trades("XXBTZUSD")
             .count(5)
             .since("0".into())
             .send()
-            .await;
+            .await
+            .unwrap();
 
-        if let Ok(trades) = trades {
-            assert!(trades.pair["BTC/USD"].len() == 5);
-        }
+        assert_eq!(trades.pair["XXBTZUSD"].len(), 2);
+        assert_eq!(trades.last, "1688671969993150842");
+
+        let requests = mock.requests().await;
+
+        assert_eq!(
+            requests[0].url.query(),
+            Some("pair=XXBTZUSD&count=5&since=0")
+        );
     }
 }
@@ -16,6 +16,11 @@ impl GetServerTimeRequest {
     pub async fn send(self) -> Result<GetServerTimeResponse> {
         self.execute().await
     }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<GetServerTimeResponse> {
+        crate::blocking::block_on(self.send())
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -34,24 +39,22 @@ impl Client {
 
 #[cfg(test)]
 mod tests {
-    use crate::{Client, JsonValue, Result};
+    use crate::mock::MockKraken;
+    use crate::{JsonValue, Result};
 
     #[tokio::test]
     async fn get_server_time() {
-        let client = Client::default();
+        let mock = MockKraken::start().await;
+        mock.serve("public/Time").await;
 
-        let resp = client.get_server_time().send().await;
+        let client = mock.client();
 
-        match resp {
-            Ok(resp) => println!("{}", resp.unixtime),
-            Err(error) => eprintln!("{:?}", error),
-        }
+        let resp = client.get_server_time().send().await.unwrap();
+
+        assert_eq!(resp.unixtime, 1688669448);
 
         let resp: Result<JsonValue> = client.get_server_time().execute().await;
 
-        match resp {
-            Ok(resp) => println!("{}", resp),
-            Err(error) => eprintln!("{:?}", error),
-        }
+        assert_eq!(resp.unwrap()["unixtime"], 1688669448);
     }
 }
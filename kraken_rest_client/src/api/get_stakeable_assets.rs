@@ -20,6 +20,11 @@ impl GetStakeableAssetsRequest {
     pub async fn send(self) -> Result<GetStakeableAssetsResponse> {
         self.execute().await
     }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<GetStakeableAssetsResponse> {
+        crate::blocking::block_on(self.send())
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -42,6 +47,7 @@ pub struct StakeableAsset {
 pub type GetStakeableAssetsResponse = Vec<StakeableAsset>;
 
 impl Client {
+    #[deprecated(note = "the legacy staking API is deprecated, use `list_earn_strategies` instead")]
     pub fn get_stakeable_assets(&self) -> GetStakeableAssetsRequest {
         GetStakeableAssetsRequest {
             client: self.clone(),
@@ -16,6 +16,11 @@ impl GetSystemStatusRequest {
     pub async fn send(self) -> Result<GetSystemStatusResponse> {
         self.execute().await
     }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<GetSystemStatusResponse> {
+        crate::blocking::block_on(self.send())
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -41,24 +46,22 @@ impl Client {
 
 #[cfg(test)]
 mod tests {
-    use crate::{Client, JsonValue, Result};
+    use crate::mock::MockKraken;
+    use crate::{JsonValue, Result};
 
     #[tokio::test]
     async fn get_system_status() {
-        let client = Client::default();
+        let mock = MockKraken::start().await;
+        mock.serve("public/SystemStatus").await;
 
-        let resp = client.get_system_status().send().await;
+        let client = mock.client();
 
-        match resp {
-            Ok(resp) => println!("{}", resp.status),
-            Err(error) => eprintln!("{:?}", error),
-        }
+        let resp = client.get_system_status().send().await.unwrap();
+
+        assert_eq!(resp.status, "online");
 
         let resp: Result<JsonValue> = client.get_system_status().execute().await;
 
-        match resp {
-            Ok(resp) => println!("{}", resp),
-            Err(error) => eprintln!("{:?}", error),
-        }
+        assert_eq!(resp.unwrap()["status"], "online");
     }
 }
@@ -31,6 +31,11 @@ impl GetTickersRequest {
     pub async fn send(self) -> Result<GetTickersResponse> {
         self.execute().await
     }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<GetTickersResponse> {
+        crate::blocking::block_on(self.send())
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -92,17 +97,19 @@ impl Client {
 
 #[cfg(test)]
 mod tests {
-    use crate::Client;
+    use crate::mock::MockKraken;
 
     #[tokio::test]
     async fn get_tickers() {
-        let client = Client::default();
+        let mock = MockKraken::start().await;
+        mock.serve("public/Ticker").await;
 
-        let resp = client.get_tickers("XXBTZUSD,DOTUSD").send().await;
+        let resp = mock.client().get_tickers("XXBTZUSD").send().await.unwrap();
 
-        match resp {
-            Ok(resp) => println!("{:?}", resp),
-            Err(error) => eprintln!("{:?}", error),
-        }
+        assert_eq!(resp["XXBTZUSD"].o, "30502.80000");
+
+        let requests = mock.requests().await;
+
+        assert_eq!(requests[0].url.query(), Some("pair=XXBTZUSD"));
     }
 }
@@ -29,6 +29,11 @@ impl GetTradeBalanceRequest {
     pub async fn send(self) -> Result<GetTradeBalanceResponse> {
         self.execute().await
     }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<GetTradeBalanceResponse> {
+        crate::blocking::block_on(self.send())
+    }
 }
 
 #[derive(Debug, Deserialize)]
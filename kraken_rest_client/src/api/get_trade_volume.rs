@@ -41,6 +41,11 @@ impl GetTradeVolumeRequest {
     pub async fn send(self) -> Result<GetTradeVolumeResponse> {
         self.execute().await
     }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<GetTradeVolumeResponse> {
+        crate::blocking::block_on(self.send())
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -1,6 +1,37 @@
-use crate::{Client, Result};
+use crate::{
+    paginate::{paginate, Page, DEFAULT_PAGE_DELAY},
+    types::order::{orderside_from_str, ordertype_from_str},
+    util::comma_separated,
+    Client, OrderSide, OrderType, Result,
+};
+use futures::Stream;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt, str::FromStr, time::Duration};
+
+/// The type of trades to retrieve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TradeType {
+    #[default]
+    All,
+    AnyPosition,
+    ClosedPosition,
+    ClosingPosition,
+    NoPosition,
+}
+
+impl fmt::Display for TradeType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let trade_type = match self {
+            Self::All => "all",
+            Self::AnyPosition => "any position",
+            Self::ClosedPosition => "closed position",
+            Self::ClosingPosition => "closing position",
+            Self::NoPosition => "no position",
+        };
+
+        write!(f, "{}", trade_type)
+    }
+}
 
 /// Retrieve information about trades/fills. 50 results are returned at a time,
 /// the most recent by default.
@@ -10,11 +41,11 @@ use std::collections::HashMap;
 ///
 /// - <https://docs.kraken.com/rest/#operation/getTradeHistory>
 /// - <https://api.kraken.com/0/private/TradesHistory>
+#[derive(Clone)]
 #[must_use = "Does nothing until you send or execute it"]
 pub struct GetTradesHistoryRequest {
     client: Client,
-    // #todo make this typed.
-    trade_type: Option<String>,
+    trade_type: Option<TradeType>,
     trades: Option<bool>,
     // #todo support order txid
     /// starting unix timestamp or order tx id of results (exclusive)
@@ -26,9 +57,10 @@ pub struct GetTradesHistoryRequest {
 }
 
 impl GetTradesHistoryRequest {
-    pub fn trade_type(self, trades: bool) -> Self {
+    /// Type of trades (default = all)
+    pub fn trade_type(self, trade_type: TradeType) -> Self {
         Self {
-            trades: Some(trades),
+            trade_type: Some(trade_type),
             ..self
         }
     }
@@ -66,7 +98,10 @@ impl GetTradesHistoryRequest {
         let mut query: Vec<String> = Vec::new();
 
         if let Some(trade_type) = self.trade_type {
-            query.push(format!("type={}", trade_type));
+            query.push(format!(
+                "type={}",
+                urlencoding::encode(&trade_type.to_string())
+            ));
         }
 
         if let Some(true) = self.trades {
@@ -99,24 +134,103 @@ impl GetTradesHistoryRequest {
     pub async fn send(self) -> Result<GetTradesHistoryResponse> {
         self.execute().await
     }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<GetTradesHistoryResponse> {
+        crate::blocking::block_on(self.send())
+    }
+
+    /// Returns a stream of all trades matching the request, fetching the
+    /// result pages as needed.
+    pub fn stream(self) -> impl Stream<Item = Result<(String, TradeInfo)>> {
+        self.stream_with_page_delay(DEFAULT_PAGE_DELAY)
+    }
+
+    /// Like `stream`, with a custom pause between page requests.
+    pub fn stream_with_page_delay(
+        self,
+        page_delay: Duration,
+    ) -> impl Stream<Item = Result<(String, TradeInfo)>> {
+        paginate(move |ofs| self.clone().ofs(ofs).send(), page_delay)
+    }
+}
+
+/// Additional information about a trade, reported in the comma delimited
+/// `misc` field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Ord, PartialOrd, Hash)]
+pub enum TradeMisc {
+    /// Trade closes all or part of a position
+    Closing,
+}
+
+impl fmt::Display for TradeMisc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Closing => write!(f, "closing"),
+        }
+    }
+}
+
+impl FromStr for TradeMisc {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "closing" => Ok(Self::Closing),
+            _ => Err(format!("Invalid trade misc: {}", s)),
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct TradeInfo {
+    /// Order responsible for execution of trade
     pub ordertxid: String,
+    /// Position responsible for execution of trade
     pub postxid: Option<String>,
     pub pair: String,
+    /// Unix timestamp of trade
     pub time: f64,
     #[serde(rename = "type")]
-    pub orderside: String,
-    pub ordertype: String,
+    #[serde(deserialize_with = "orderside_from_str")]
+    pub orderside: OrderSide,
+    #[serde(deserialize_with = "ordertype_from_str")]
+    pub ordertype: OrderType,
+    /// Average price order was executed at (quote currency)
     pub price: String,
+    /// Total cost of order (quote currency)
     pub cost: String,
+    /// Total fee (quote currency)
     pub fee: String,
+    /// Volume (base currency)
     pub vol: String,
+    /// Initial margin (quote currency)
     pub margin: String,
-    pub misc: String,
-    // #todo add position related fields.
+    /// Amount of leverage used in trade
+    pub leverage: Option<String>,
+    #[serde(with = "comma_separated", default)]
+    pub misc: Vec<TradeMisc>,
+    /// IDs of the ledger entries related to the trade, if requested
+    pub ledgers: Option<Vec<String>>,
+    pub trade_id: Option<i64>,
+    /// True if the trade was a maker trade
+    pub maker: Option<bool>,
+    /// Position status (open/closed), only for trades opening a position
+    pub posstatus: Option<String>,
+    /// Average price of closed portion of position (quote currency)
+    pub cprice: Option<String>,
+    /// Total cost of closed portion of position (quote currency)
+    pub ccost: Option<String>,
+    /// Total fee of closed portion of position (quote currency)
+    pub cfee: Option<String>,
+    /// Total volume of closed portion of position (quote currency)
+    pub cvol: Option<String>,
+    /// Total margin freed in closed portion of position (quote currency)
+    pub cmargin: Option<String>,
+    /// Net profit/loss of closed portion of position (quote currency)
+    pub net: Option<String>,
+    /// IDs of the trades closing the position
+    pub trades: Option<Vec<String>>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -125,6 +239,20 @@ pub struct GetTradesHistoryResponse {
     pub count: usize,
 }
 
+impl Page for GetTradesHistoryResponse {
+    type Item = TradeInfo;
+
+    fn count(&self) -> usize {
+        self.count
+    }
+
+    fn into_entries(self) -> Vec<(String, TradeInfo)> {
+        let mut entries: Vec<_> = self.trades.into_iter().collect();
+        entries.sort_by(|(_, a), (_, b)| b.time.total_cmp(&a.time));
+        entries
+    }
+}
+
 impl Client {
     pub fn get_trades_history(&self) -> GetTradesHistoryRequest {
         GetTradesHistoryRequest {
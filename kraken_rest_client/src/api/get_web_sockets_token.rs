@@ -22,6 +22,11 @@ impl GetWebSocketsTokenRequest {
     pub async fn send(self) -> Result<GetWebSocketsTokenResponse> {
         self.execute().await
     }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<GetWebSocketsTokenResponse> {
+        crate::blocking::block_on(self.send())
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -65,6 +65,11 @@ impl GetWithdrawalAddressesRequest {
     pub async fn send(self) -> Result<GetWithdrawalAddressesResponse> {
         self.execute().await
     }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<GetWithdrawalAddressesResponse> {
+        crate::blocking::block_on(self.send())
+    }
 }
 
 #[derive(Debug, Deserialize)]
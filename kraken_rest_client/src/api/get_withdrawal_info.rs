@@ -0,0 +1,70 @@
+use crate::{Client, Result};
+use serde::{de::DeserializeOwned, Deserialize};
+
+/// Retrieve fee information about potential withdrawals for a particular
+/// asset, key and amount.
+///
+/// - <https://docs.kraken.com/rest/#tag/Funding/operation/getWithdrawalInformation>
+/// - <https://api.kraken.com/0/private/WithdrawInfo>
+#[must_use = "Does nothing until you send or execute it"]
+pub struct GetWithdrawalInfoRequest {
+    client: Client,
+    /// Asset being withdrawn
+    asset: String,
+    /// Withdrawal key name, as set up on your account
+    key: String,
+    /// Amount to be withdrawn
+    amount: String,
+}
+
+impl GetWithdrawalInfoRequest {
+    pub async fn execute<T: DeserializeOwned>(self) -> Result<T> {
+        let query = format!(
+            "asset={}&key={}&amount={}",
+            self.asset,
+            urlencoding::encode(&self.key),
+            self.amount
+        );
+
+        self.client
+            .send_private("/0/private/WithdrawInfo", Some(query))
+            .await
+    }
+
+    pub async fn send(self) -> Result<GetWithdrawalInfoResponse> {
+        self.execute().await
+    }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<GetWithdrawalInfoResponse> {
+        crate::blocking::block_on(self.send())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GetWithdrawalInfoResponse {
+    /// Name of the withdrawal method that will be used
+    pub method: String,
+    /// Maximum net amount that can be withdrawn right now
+    pub limit: String,
+    /// Net amount that will be sent, after fees
+    pub amount: String,
+    /// Amount of fees that will be paid
+    pub fee: String,
+}
+
+impl Client {
+    pub fn get_withdrawal_info(
+        &self,
+        asset: &str,
+        key: &str,
+        amount: &str,
+    ) -> GetWithdrawalInfoRequest {
+        GetWithdrawalInfoRequest {
+            client: self.clone(),
+            asset: asset.to_string(),
+            key: key.to_string(),
+            amount: amount.to_string(),
+        }
+    }
+}
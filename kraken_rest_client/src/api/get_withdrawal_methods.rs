@@ -65,6 +65,11 @@ impl GetWithdrawMethodsRequest {
     pub async fn send(self) -> Result<GetWithdrawMethodsResponse> {
         self.execute().await
     }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<GetWithdrawMethodsResponse> {
+        crate::blocking::block_on(self.send())
+    }
 }
 
 #[derive(Debug, Deserialize)]
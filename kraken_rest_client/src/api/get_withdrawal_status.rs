@@ -0,0 +1,266 @@
+use crate::{error::Error, Client, FundingStatus, FundingStatusProp, Result};
+use serde::{de::DeserializeOwned, Deserialize};
+use std::time::Duration;
+
+/// The number of polls `wait_for_withdrawal` waits for a new withdrawal to
+/// show up in the status list.
+const MAX_MISSING_WITHDRAWAL_POLLS: usize = 5;
+
+/// Retrieve information about recently requested withdrawals.
+///
+/// - <https://docs.kraken.com/rest/#tag/Funding/operation/getStatusRecentWithdrawals>
+/// - <https://api.kraken.com/0/private/WithdrawStatus>
+#[derive(Clone)]
+#[must_use = "Does nothing until you send or execute it"]
+pub struct GetWithdrawalStatusRequest {
+    client: Client,
+    /// Filter for specific asset being withdrawn
+    asset: Option<String>,
+    /// Filter for specific asset class being withdrawn
+    aclass: Option<String>,
+    /// Filter for specific name of withdrawal method
+    method: Option<String>,
+    /// Start timestamp, withdrawals created strictly before will not be
+    /// included in the response
+    start: Option<i64>,
+    /// End timestamp, withdrawals created strictly after will be not be
+    /// included in the response
+    end: Option<i64>,
+}
+
+impl GetWithdrawalStatusRequest {
+    pub fn asset(self, asset: impl Into<String>) -> Self {
+        Self {
+            asset: Some(asset.into()),
+            ..self
+        }
+    }
+
+    pub fn aclass(self, aclass: impl Into<String>) -> Self {
+        Self {
+            aclass: Some(aclass.into()),
+            ..self
+        }
+    }
+
+    pub fn method(self, method: impl Into<String>) -> Self {
+        Self {
+            method: Some(method.into()),
+            ..self
+        }
+    }
+
+    pub fn start(self, start: i64) -> Self {
+        Self {
+            start: Some(start),
+            ..self
+        }
+    }
+
+    pub fn end(self, end: i64) -> Self {
+        Self {
+            end: Some(end),
+            ..self
+        }
+    }
+
+    pub async fn execute<T: DeserializeOwned>(self) -> Result<T> {
+        let mut query: Vec<String> = Vec::new();
+
+        if let Some(asset) = &self.asset {
+            query.push(format!("asset={}", asset));
+        }
+
+        if let Some(aclass) = &self.aclass {
+            query.push(format!("aclass={}", aclass));
+        }
+
+        if let Some(method) = &self.method {
+            query.push(format!("method={}", urlencoding::encode(method)));
+        }
+
+        if let Some(start) = self.start {
+            query.push(format!("start={}", start));
+        }
+
+        if let Some(end) = self.end {
+            query.push(format!("end={}", end));
+        }
+
+        let query = if query.is_empty() {
+            None
+        } else {
+            Some(query.join("&"))
+        };
+
+        self.client
+            .send_private("/0/private/WithdrawStatus", query)
+            .await
+    }
+
+    pub async fn send(self) -> Result<GetWithdrawalStatusResponse> {
+        self.execute().await
+    }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<GetWithdrawalStatusResponse> {
+        crate::blocking::block_on(self.send())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WithdrawalStatus {
+    /// Name of withdrawal method
+    pub method: String,
+    /// Network name based on the funding method used
+    pub network: Option<String>,
+    /// Asset class
+    pub aclass: String,
+    /// Asset
+    pub asset: String,
+    /// Reference ID
+    pub refid: String,
+    /// Method transaction ID
+    pub txid: Option<String>,
+    /// Method transaction information
+    pub info: String,
+    /// Amount withdrawn
+    pub amount: String,
+    /// Fees paid
+    pub fee: String,
+    /// Unix timestamp when request was made
+    pub time: u64,
+    /// Status of withdrawal
+    pub status: FundingStatus,
+    /// Additional status properties (if available)
+    #[serde(rename = "status-prop")]
+    pub status_prop: Option<FundingStatusProp>,
+    /// Withdrawal key name, as set up on your account
+    pub key: Option<String>,
+}
+
+impl WithdrawalStatus {
+    /// Returns true if the withdrawal succeeded, failed or was canceled.
+    pub fn is_final(&self) -> bool {
+        self.status.is_final() || self.status_prop == Some(FundingStatusProp::Canceled)
+    }
+}
+
+pub type GetWithdrawalStatusResponse = Vec<WithdrawalStatus>;
+
+impl Client {
+    pub fn get_withdrawal_status(&self) -> GetWithdrawalStatusRequest {
+        GetWithdrawalStatusRequest {
+            client: self.clone(),
+            asset: None,
+            aclass: None,
+            method: None,
+            start: None,
+            end: None,
+        }
+    }
+
+    /// Polls the status of the withdrawal with the given `refid` every
+    /// `poll_interval` until it succeeds, fails or is canceled. Fails if the
+    /// withdrawal is not listed in the recent withdrawals, e.g. an unknown
+    /// `refid` or one that dropped out of the listed window. Wrap in
+    /// `tokio::time::timeout` to bound the wait.
+    pub async fn wait_for_withdrawal(
+        &self,
+        asset: &str,
+        refid: &str,
+        poll_interval: Duration,
+    ) -> Result<WithdrawalStatus> {
+        let mut seen = false;
+        let mut missing_polls = 0;
+
+        loop {
+            let withdrawal = self
+                .get_withdrawal_status()
+                .asset(asset)
+                .send()
+                .await?
+                .into_iter()
+                .find(|withdrawal| withdrawal.refid == refid);
+
+            match withdrawal {
+                Some(withdrawal) if withdrawal.is_final() => return Ok(withdrawal),
+                Some(_) => seen = true,
+                // A new withdrawal may take a few polls to be listed.
+                None if !seen && missing_polls + 1 < MAX_MISSING_WITHDRAWAL_POLLS => {
+                    missing_polls += 1;
+                }
+                None => {
+                    return Err(Error::internal(format!(
+                        "withdrawal `{refid}` is not listed in the recent withdrawals"
+                    )));
+                }
+            }
+
+            tokio::time::sleep(poll_interval).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GetWithdrawalStatusResponse, MAX_MISSING_WITHDRAWAL_POLLS};
+    use crate::mock::MockKraken;
+    use crate::{FundingStatus, FundingStatusProp};
+    use std::time::Duration;
+
+    #[test]
+    fn deserialize_withdrawal_status() {
+        let json = r#"[
+            {
+                "method": "Bitcoin",
+                "aclass": "currency",
+                "asset": "XXBT",
+                "refid": "FTQcuak-V6Za8qrWnhzTx67yYHz8Tg",
+                "txid": "THVRQM-33VKH-UCI7BS",
+                "info": "mzp6yUVMRxfasyfwzTZjjy38dHqMX7Z3GR",
+                "amount": "0.72485000",
+                "fee": "0.00020000",
+                "time": 1688014586,
+                "status": "Pending",
+                "key": "btc-wallet-1"
+            },
+            {
+                "method": "Bitcoin",
+                "aclass": "currency",
+                "asset": "XXBT",
+                "refid": "FTQcuak-V6Za8qrPnhsTx47yYLz8Tg",
+                "txid": null,
+                "info": "mzp6yUVMRxfasyfwzTZjjy38dHqMX7Z3GR",
+                "amount": "0.72485000",
+                "fee": "0.00020000",
+                "time": 1688015423,
+                "status": "Failure",
+                "status-prop": "canceled",
+                "key": "btc-wallet-2"
+            }
+        ]"#;
+
+        let resp: GetWithdrawalStatusResponse = serde_json::from_str(json).unwrap();
+
+        assert_eq!(resp[0].status, FundingStatus::Pending);
+        assert!(!resp[0].is_final());
+        assert_eq!(resp[1].status_prop, Some(FundingStatusProp::Canceled));
+        assert!(resp[1].txid.is_none());
+        assert!(resp[1].is_final());
+    }
+
+    #[tokio::test]
+    async fn wait_for_withdrawal_fails_if_not_listed() {
+        let mock = MockKraken::start().await;
+        mock.serve("private/WithdrawStatus").await;
+
+        let result = mock
+            .client()
+            .wait_for_withdrawal("XXBT", "FTQcuak-unknown", Duration::ZERO)
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(mock.requests().await.len(), MAX_MISSING_WITHDRAWAL_POLLS);
+    }
+}
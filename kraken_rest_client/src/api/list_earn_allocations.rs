@@ -0,0 +1,212 @@
+use crate::{Client, Result};
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::{Map, Value};
+
+/// List all allocations for the user.
+///
+/// By default all allocations are returned, even for strategies that have
+/// been used in the past and have zero balance now.
+///
+/// - <https://docs.kraken.com/rest/#tag/Earn/operation/listAllocations>
+/// - <https://api.kraken.com/0/private/Earn/Allocations>
+#[must_use = "Does nothing until you send or execute it"]
+pub struct ListEarnAllocationsRequest {
+    client: Client,
+    ascending: Option<bool>,
+    converted_asset: Option<String>,
+    hide_zero_allocations: Option<bool>,
+}
+
+impl ListEarnAllocationsRequest {
+    /// `true` to sort ascending, `false` (the default) for descending.
+    pub fn ascending(self, ascending: bool) -> Self {
+        Self {
+            ascending: Some(ascending),
+            ..self
+        }
+    }
+
+    /// A secondary currency to express the value of your allocations
+    /// (default = USD)
+    pub fn converted_asset(self, converted_asset: impl Into<String>) -> Self {
+        Self {
+            converted_asset: Some(converted_asset.into()),
+            ..self
+        }
+    }
+
+    /// Omit entries for strategies that were used in the past but now they
+    /// don't hold any allocation (default = false)
+    pub fn hide_zero_allocations(self, hide_zero_allocations: bool) -> Self {
+        Self {
+            hide_zero_allocations: Some(hide_zero_allocations),
+            ..self
+        }
+    }
+
+    pub async fn execute<T: DeserializeOwned>(self) -> Result<T> {
+        let mut params = Map::new();
+
+        if let Some(ascending) = self.ascending {
+            params.insert("ascending".into(), ascending.into());
+        }
+
+        if let Some(converted_asset) = self.converted_asset {
+            params.insert("converted_asset".into(), converted_asset.into());
+        }
+
+        if let Some(hide_zero_allocations) = self.hide_zero_allocations {
+            params.insert("hide_zero_allocations".into(), hide_zero_allocations.into());
+        }
+
+        self.client
+            .send_private_json("/0/private/Earn/Allocations", Value::Object(params))
+            .await
+    }
+
+    pub async fn send(self) -> Result<ListEarnAllocationsResponse> {
+        self.execute().await
+    }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<ListEarnAllocationsResponse> {
+        crate::blocking::block_on(self.send())
+    }
+}
+
+/// An amount in the native asset and in the converted asset.
+#[derive(Debug, Deserialize)]
+pub struct EarnAmount {
+    pub native: String,
+    pub converted: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EarnAllocation {
+    pub created_at: String,
+    /// The time the allocation leaves the current state
+    pub expires: String,
+    pub native: String,
+    pub converted: String,
+}
+
+/// Amounts in a transitional state, e.g. bonding or unbonding.
+#[derive(Debug, Deserialize)]
+pub struct EarnAllocationState {
+    pub native: String,
+    pub converted: String,
+    pub allocation_count: u32,
+    #[serde(default)]
+    pub allocations: Vec<EarnAllocation>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AmountAllocated {
+    pub bonding: Option<EarnAllocationState>,
+    pub exit_queue: Option<EarnAllocationState>,
+    pub pending: Option<EarnAmount>,
+    pub unbonding: Option<EarnAllocationState>,
+    pub total: EarnAmount,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EarnPayout {
+    pub period_start: String,
+    pub period_end: String,
+    /// Reward accumulated in the payout period until now
+    pub accumulated_reward: EarnAmount,
+    /// Estimated reward from now until the payout
+    pub estimated_reward: EarnAmount,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EarnAllocationInfo {
+    pub strategy_id: String,
+    /// The asset of the native currency of this allocation
+    pub native_asset: String,
+    pub amount_allocated: AmountAllocated,
+    /// Amount earned using the strategy during the whole lifetime of user
+    pub total_rewarded: EarnAmount,
+    /// Information about the current payout period, absent if there is no
+    /// current payout period
+    pub payout: Option<EarnPayout>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListEarnAllocationsResponse {
+    /// A secondary asset to show the value of allocations
+    pub converted_asset: String,
+    /// The total amount allocated across all strategies, denominated in the
+    /// `converted_asset` currency
+    pub total_allocated: String,
+    /// Amount earned across all strategies during the whole lifetime of user,
+    /// denominated in `converted_asset` currency
+    pub total_rewarded: String,
+    pub items: Vec<EarnAllocationInfo>,
+}
+
+impl Client {
+    pub fn list_earn_allocations(&self) -> ListEarnAllocationsRequest {
+        ListEarnAllocationsRequest {
+            client: self.clone(),
+            ascending: None,
+            converted_asset: None,
+            hide_zero_allocations: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ListEarnAllocationsResponse;
+
+    #[test]
+    fn deserialize_earn_allocations() {
+        let json = r#"{
+            "converted_asset": "USD",
+            "total_allocated": "49.2398",
+            "total_rewarded": "0.0675",
+            "items": [
+                {
+                    "strategy_id": "ESDQCOL-WTZEU-NU55QF",
+                    "native_asset": "ETH",
+                    "amount_allocated": {
+                        "bonding": {
+                            "native": "0.0210000000",
+                            "converted": "39.0645",
+                            "allocation_count": 2,
+                            "allocations": [
+                                {
+                                    "created_at": "2023-07-06T10:52:05Z",
+                                    "expires": "2023-08-19T02:34:05.807Z",
+                                    "native": "0.0010000000",
+                                    "converted": "1.8602"
+                                }
+                            ]
+                        },
+                        "total": {
+                            "native": "0.0210000000",
+                            "converted": "39.0645"
+                        }
+                    },
+                    "total_rewarded": {
+                        "native": "0",
+                        "converted": "0.0000"
+                    }
+                }
+            ]
+        }"#;
+
+        let resp: ListEarnAllocationsResponse = serde_json::from_str(json).unwrap();
+
+        assert_eq!(resp.total_allocated, "49.2398");
+
+        let allocation = &resp.items[0];
+        let bonding = allocation.amount_allocated.bonding.as_ref().unwrap();
+
+        assert_eq!(bonding.allocation_count, 2);
+        assert_eq!(bonding.allocations[0].native, "0.0010000000");
+        assert_eq!(allocation.amount_allocated.total.converted, "39.0645");
+        assert!(allocation.payout.is_none());
+    }
+}
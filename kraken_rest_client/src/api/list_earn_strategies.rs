@@ -0,0 +1,326 @@
+use crate::{
+    paginate::retry_rate_limited,
+    util::{optional_string_or_number, string_or_number},
+    Client, Result, DEFAULT_PAGE_DELAY,
+};
+use futures::{stream, Stream};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::time::Duration;
+
+/// List earn strategies along with their parameters. Returns only strategies
+/// that are available to the user based on geographic region.
+///
+/// - <https://docs.kraken.com/rest/#tag/Earn/operation/listStrategies>
+/// - <https://api.kraken.com/0/private/Earn/Strategies>
+#[derive(Clone)]
+#[must_use = "Does nothing until you send or execute it"]
+pub struct ListEarnStrategiesRequest {
+    client: Client,
+    ascending: Option<bool>,
+    asset: Option<String>,
+    cursor: Option<String>,
+    limit: Option<u16>,
+    lock_type: Option<Vec<LockTypeKind>>,
+}
+
+impl ListEarnStrategiesRequest {
+    /// `true` to sort ascending, `false` (the default) for descending.
+    pub fn ascending(self, ascending: bool) -> Self {
+        Self {
+            ascending: Some(ascending),
+            ..self
+        }
+    }
+
+    /// Filter strategies by asset name
+    pub fn asset(self, asset: impl Into<String>) -> Self {
+        Self {
+            asset: Some(asset.into()),
+            ..self
+        }
+    }
+
+    /// None to start at beginning/end, otherwise next page ID
+    pub fn cursor(self, cursor: impl Into<String>) -> Self {
+        Self {
+            cursor: Some(cursor.into()),
+            ..self
+        }
+    }
+
+    /// How many items to return per page. Note that the limit may be capped to
+    /// lower value in the application code.
+    pub fn limit(self, limit: u16) -> Self {
+        Self {
+            limit: Some(limit),
+            ..self
+        }
+    }
+
+    /// Filter strategies by lock type
+    pub fn lock_type(self, lock_type: impl Into<Vec<LockTypeKind>>) -> Self {
+        Self {
+            lock_type: Some(lock_type.into()),
+            ..self
+        }
+    }
+
+    pub async fn execute<T: DeserializeOwned>(self) -> Result<T> {
+        let mut params = Map::new();
+
+        if let Some(ascending) = self.ascending {
+            params.insert("ascending".into(), ascending.into());
+        }
+
+        if let Some(asset) = self.asset {
+            params.insert("asset".into(), asset.into());
+        }
+
+        if let Some(cursor) = self.cursor {
+            params.insert("cursor".into(), cursor.into());
+        }
+
+        if let Some(limit) = self.limit {
+            params.insert("limit".into(), limit.into());
+        }
+
+        if let Some(lock_type) = self.lock_type {
+            params.insert("lock_type".into(), serde_json::to_value(lock_type)?);
+        }
+
+        self.client
+            .send_private_json("/0/private/Earn/Strategies", Value::Object(params))
+            .await
+    }
+
+    pub async fn send(self) -> Result<ListEarnStrategiesResponse> {
+        self.execute().await
+    }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<ListEarnStrategiesResponse> {
+        crate::blocking::block_on(self.send())
+    }
+
+    /// Returns a stream of all strategies matching the request, following the
+    /// `next_cursor` of each page.
+    pub fn stream(self) -> impl Stream<Item = Result<EarnStrategy>> {
+        self.stream_with_page_delay(DEFAULT_PAGE_DELAY)
+    }
+
+    /// Like `stream`, with a custom pause between page requests.
+    pub fn stream_with_page_delay(
+        self,
+        page_delay: Duration,
+    ) -> impl Stream<Item = Result<EarnStrategy>> {
+        stream::unfold(
+            (Some(self), Vec::new().into_iter(), false),
+            move |(mut req, mut buffer, mut started)| async move {
+                loop {
+                    if let Some(strategy) = buffer.next() {
+                        return Some((Ok(strategy), (req, buffer, started)));
+                    }
+
+                    let current = req.take()?;
+
+                    if started && !page_delay.is_zero() {
+                        tokio::time::sleep(page_delay).await;
+                    }
+
+                    started = true;
+
+                    match retry_rate_limited(|| current.clone().send(), page_delay).await {
+                        Ok(resp) => {
+                            req = resp.next_cursor.map(|cursor| current.cursor(cursor));
+                            buffer = resp.items.into_iter();
+                        }
+                        Err(error) => return Some((Err(error), (None, buffer, started))),
+                    }
+                }
+            },
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LockTypeKind {
+    Flex,
+    Bonded,
+    Timed,
+    Instant,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LockType {
+    #[serde(rename = "type")]
+    pub lock_type: LockTypeKind,
+    /// At what intervals are rewards distributed and credited to the user's
+    /// ledger, in seconds
+    pub payout_frequency: Option<u64>,
+    /// Duration of the bonding period, in seconds
+    pub bonding_period: Option<u64>,
+    /// Is the bonding period length variable (`true`) or static (`false`)
+    pub bonding_period_variable: Option<bool>,
+    /// Whether rewards are earned during the bonding period
+    pub bonding_rewards: Option<bool>,
+    /// In order to remove funds, if this value is greater than 0, funds will
+    /// first have to enter an exit queue and will have to wait for the exit
+    /// queue period to end, in seconds
+    pub exit_queue_period: Option<u64>,
+    /// Duration of the unbonding period, in seconds
+    pub unbonding_period: Option<u64>,
+    /// Is the unbonding period length variable (`true`) or static (`false`)
+    pub unbonding_period_variable: Option<bool>,
+    /// Whether rewards are earned and paid out during the unbonding period
+    pub unbonding_rewards: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AprEstimate {
+    pub low: String,
+    pub high: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AutoCompoundKind {
+    Enabled,
+    Disabled,
+    Optional,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AutoCompound {
+    #[serde(rename = "type")]
+    pub auto_compound_type: AutoCompoundKind,
+    /// Whether auto compounding is enabled by default, if optional
+    pub default: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum YieldSourceKind {
+    Staking,
+    OffChain,
+    OptInRewards,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct YieldSource {
+    #[serde(rename = "type")]
+    pub yield_source_type: YieldSourceKind,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EarnStrategy {
+    /// The unique identifier for this strategy
+    pub id: String,
+    /// The asset to invest for this earn strategy
+    pub asset: String,
+    pub lock_type: LockType,
+    /// The estimated APR range
+    pub apr_estimate: Option<AprEstimate>,
+    /// Minimum amount (in USD) for an allocation or deallocation
+    #[serde(default, deserialize_with = "optional_string_or_number")]
+    pub user_min_allocation: Option<String>,
+    /// Maximum amount (in USD) the user can allocate
+    #[serde(default, deserialize_with = "optional_string_or_number")]
+    pub user_cap: Option<String>,
+    /// Fee applied when allocating to this strategy
+    #[serde(deserialize_with = "string_or_number")]
+    pub allocation_fee: String,
+    /// Fee applied when deallocating from this strategy
+    #[serde(deserialize_with = "string_or_number")]
+    pub deallocation_fee: String,
+    pub auto_compound: AutoCompound,
+    pub yield_source: YieldSource,
+    /// Is allocation available for this strategy
+    pub can_allocate: bool,
+    /// Is deallocation available for this strategy
+    pub can_deallocate: bool,
+    /// Reason list why user is not eligible for allocating to the strategy
+    #[serde(default)]
+    pub allocation_restriction_info: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListEarnStrategiesResponse {
+    pub items: Vec<EarnStrategy>,
+    /// Index to send into `cursor` for next page, absent means end of list
+    pub next_cursor: Option<String>,
+}
+
+impl Client {
+    pub fn list_earn_strategies(&self) -> ListEarnStrategiesRequest {
+        ListEarnStrategiesRequest {
+            client: self.clone(),
+            ascending: None,
+            asset: None,
+            cursor: None,
+            limit: None,
+            lock_type: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AutoCompoundKind, ListEarnStrategiesResponse, LockTypeKind};
+
+    #[test]
+    fn deserialize_earn_strategies() {
+        let json = r#"{
+            "items": [
+                {
+                    "id": "ESRFUO3-Q62XD-WIOIL7",
+                    "asset": "DOT",
+                    "lock_type": {
+                        "type": "instant",
+                        "payout_frequency": 604800
+                    },
+                    "apr_estimate": {
+                        "low": "8.0000",
+                        "high": "12.0000"
+                    },
+                    "user_min_allocation": "0.01",
+                    "allocation_fee": "0.0000",
+                    "deallocation_fee": 0,
+                    "auto_compound": {
+                        "type": "enabled"
+                    },
+                    "yield_source": {
+                        "type": "staking"
+                    },
+                    "can_allocate": true,
+                    "can_deallocate": true,
+                    "allocation_restriction_info": []
+                }
+            ],
+            "next_cursor": "2"
+        }"#;
+
+        let resp: ListEarnStrategiesResponse = serde_json::from_str(json).unwrap();
+
+        assert_eq!(resp.next_cursor.as_deref(), Some("2"));
+
+        let strategy = &resp.items[0];
+
+        assert_eq!(strategy.lock_type.lock_type, LockTypeKind::Instant);
+        assert_eq!(strategy.lock_type.payout_frequency, Some(604800));
+        assert_eq!(strategy.apr_estimate.as_ref().unwrap().high, "12.0000");
+        assert_eq!(strategy.user_min_allocation.as_deref(), Some("0.01"));
+        assert_eq!(strategy.deallocation_fee, "0");
+        assert_eq!(
+            strategy.auto_compound.auto_compound_type,
+            AutoCompoundKind::Enabled
+        );
+    }
+}
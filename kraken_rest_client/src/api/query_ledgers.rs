@@ -0,0 +1,117 @@
+use crate::{api::LedgerEntry, Client, Result};
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+
+/// The maximum number of ledger ids per request.
+const MAX_IDS_PER_REQUEST: usize = 20;
+
+/// Retrieve information about specific ledger entries.
+///
+/// - <https://docs.kraken.com/rest/#tag/Account-Data/operation/getLedgersInfo>
+/// - <https://api.kraken.com/0/private/QueryLedgers>
+#[must_use = "Does nothing until you send or execute it"]
+pub struct QueryLedgersRequest {
+    client: Client,
+    /// Ledger IDs to query info about
+    id: Vec<String>,
+    /// Whether or not to include trades related to position in output (default = false)
+    trades: Option<bool>,
+}
+
+impl QueryLedgersRequest {
+    /// Whether or not to include trades related to position in output (default = false)
+    pub fn trades(self, trades: bool) -> Self {
+        Self {
+            trades: Some(trades),
+            ..self
+        }
+    }
+
+    /// Sends a single request for all ids, the API accepts up to 20.
+    pub async fn execute<T: DeserializeOwned>(self) -> Result<T> {
+        self.execute_ids(&self.id).await
+    }
+
+    async fn execute_ids<T: DeserializeOwned>(&self, ids: &[String]) -> Result<T> {
+        let mut query: Vec<String> = vec![format!("id={}", ids.join(","))];
+
+        if let Some(true) = self.trades {
+            query.push(String::from("trades=true"));
+        }
+
+        self.client
+            .send_private("/0/private/QueryLedgers", Some(query.join("&")))
+            .await
+    }
+
+    /// Sends one request per 20 ids and merges the results.
+    pub async fn send(self) -> Result<QueryLedgersResponse> {
+        let mut entries = QueryLedgersResponse::new();
+
+        for ids in self.id.chunks(MAX_IDS_PER_REQUEST) {
+            entries.extend(self.execute_ids::<QueryLedgersResponse>(ids).await?);
+        }
+
+        Ok(entries)
+    }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<QueryLedgersResponse> {
+        crate::blocking::block_on(self.send())
+    }
+}
+
+pub type QueryLedgersResponse = HashMap<String, LedgerEntry>;
+
+impl Client {
+    /// Retrieve information about specific ledger entries, given a comma
+    /// delimited list of ledger ids. Larger id sets are split into several
+    /// requests by `send`.
+    pub fn query_ledgers(&self, id: &str) -> QueryLedgersRequest {
+        QueryLedgersRequest {
+            client: self.clone(),
+            id: id
+                .split(',')
+                .map(str::trim)
+                .filter(|id| !id.is_empty())
+                .map(str::to_string)
+                .collect(),
+            trades: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mock::MockKraken;
+
+    #[tokio::test]
+    async fn query_ledgers_in_chunks() {
+        let mock = MockKraken::start().await;
+        mock.serve("private/QueryLedgers").await;
+
+        let ids: Vec<String> = (0..25).map(|i| format!("L{i:05}-AAAAA-AAAAAA")).collect();
+
+        let entries = mock
+            .client()
+            .query_ledgers(&ids.join(","))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(entries["L4UESK-KG3EQ-UFO4T5"].amount, "-24.5000");
+
+        let requests = mock.requests().await;
+
+        assert_eq!(requests.len(), 2);
+
+        let sent_ids = |i: usize| {
+            let body = String::from_utf8(requests[i].body.clone()).unwrap();
+            let (id, _) = body.strip_prefix("id=").unwrap().split_once('&').unwrap();
+            id.split(',').count()
+        };
+
+        assert_eq!(sent_ids(0), 20);
+        assert_eq!(sent_ids(1), 5);
+    }
+}
@@ -1,5 +1,5 @@
-use crate::{Client, OrderDescription, Result};
-use serde::{de::DeserializeOwned, Deserialize};
+use crate::{Client, OrderInfo, Result};
+use serde::de::DeserializeOwned;
 use std::collections::HashMap;
 
 /// - <https://docs.kraken.com/rest/#operation/getOrdersInfo>
@@ -56,25 +56,11 @@ impl QueryOrdersInfoRequest {
     pub async fn send(self) -> Result<QueryOrdersInfoResponse> {
         self.execute().await
     }
-}
 
-#[derive(Debug, Deserialize)]
-pub struct OrderInfo {
-    pub userref: Option<i32>,
-    pub status: String,
-    pub descr: OrderDescription,
-    pub oflags: String,
-    pub opentm: f64,
-    pub expiretm: f64,
-    pub vol: String,
-    pub vol_exec: String,
-    pub cost: String,
-    pub fee: String,
-    pub misc: String,
-    pub price: String,
-    pub limitprice: String,
-    pub refid: Option<String>,
-    pub reason: Option<String>,
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<QueryOrdersInfoResponse> {
+        crate::blocking::block_on(self.send())
+    }
 }
 
 pub type QueryOrdersInfoResponse = HashMap<String, OrderInfo>;
@@ -0,0 +1,90 @@
+use crate::{api::TradeInfo, Client, Result};
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+
+/// Retrieve information about specific trades/fills.
+///
+/// - <https://docs.kraken.com/rest/#tag/Account-Data/operation/getTradesInfo>
+/// - <https://api.kraken.com/0/private/QueryTrades>
+#[must_use = "Does nothing until you send or execute it"]
+pub struct QueryTradesRequest {
+    client: Client,
+    /// Comma delimited list of transaction IDs to query info about (20 maximum)
+    txid: String,
+    /// Whether or not to include trades related to position in output (default = false)
+    trades: Option<bool>,
+}
+
+impl QueryTradesRequest {
+    /// Whether or not to include trades related to position in output (default = false)
+    pub fn trades(self, trades: bool) -> Self {
+        Self {
+            trades: Some(trades),
+            ..self
+        }
+    }
+
+    pub async fn execute<T: DeserializeOwned>(self) -> Result<T> {
+        let mut query: Vec<String> = vec![format!("txid={}", self.txid)];
+
+        if let Some(true) = self.trades {
+            query.push(String::from("trades=true"));
+        }
+
+        self.client
+            .send_private("/0/private/QueryTrades", Some(query.join("&")))
+            .await
+    }
+
+    pub async fn send(self) -> Result<QueryTradesResponse> {
+        self.execute().await
+    }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<QueryTradesResponse> {
+        crate::blocking::block_on(self.send())
+    }
+}
+
+pub type QueryTradesResponse = HashMap<String, TradeInfo>;
+
+impl Client {
+    /// Retrieve information about specific trades, given a comma delimited
+    /// list of up to 20 trade ids.
+    pub fn query_trades(&self, txid: &str) -> QueryTradesRequest {
+        QueryTradesRequest {
+            client: self.clone(),
+            txid: txid.to_owned(),
+            trades: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mock::MockKraken;
+
+    #[tokio::test]
+    async fn query_trades() {
+        let mock = MockKraken::start().await;
+        mock.serve("private/QueryTrades").await;
+
+        let trades = mock
+            .client()
+            .query_trades("TRWCIF-3MJWU-5DYJG5,TNGJFU-5CD67-ZV3AEO")
+            .trades(true)
+            .send()
+            .await
+            .unwrap();
+
+        let trade = &trades["TRWCIF-3MJWU-5DYJG5"];
+
+        assert_eq!(trade.posstatus.as_deref(), Some("closed"));
+        assert_eq!(trade.net.as_deref(), Some("-0.3770"));
+        assert_eq!(trade.trades.as_ref().unwrap(), &["TNGJFU-5CD67-ZV3AEO"]);
+
+        let body = String::from_utf8(mock.requests().await[0].body.clone()).unwrap();
+
+        assert!(body.starts_with("txid=TRWCIF-3MJWU-5DYJG5,TNGJFU-5CD67-ZV3AEO&trades=true&nonce="));
+    }
+}
@@ -0,0 +1,75 @@
+use crate::{Client, Result};
+use serde::{de::DeserializeOwned, Deserialize};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RemoveExportType {
+    /// Cancel a queued or processing report.
+    Cancel,
+    /// Delete a processed report.
+    Delete,
+}
+
+impl fmt::Display for RemoveExportType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let remove_type = match self {
+            Self::Cancel => "cancel",
+            Self::Delete => "delete",
+        };
+
+        write!(f, "{}", remove_type)
+    }
+}
+
+/// Delete an exported trades/ledgers report, or cancel a pending one.
+///
+/// - <https://docs.kraken.com/rest/#tag/Account-Data/operation/removeExport>
+/// - <https://api.kraken.com/0/private/RemoveExport>
+#[must_use = "Does nothing until you send or execute it"]
+pub struct RemoveExportRequest {
+    client: Client,
+    /// ID of report to delete or cancel
+    id: String,
+    remove_type: RemoveExportType,
+}
+
+impl RemoveExportRequest {
+    pub async fn execute<T: DeserializeOwned>(self) -> Result<T> {
+        let query = format!("id={}&type={}", self.id, self.remove_type);
+
+        self.client
+            .send_private("/0/private/RemoveExport", Some(query))
+            .await
+    }
+
+    pub async fn send(self) -> Result<RemoveExportResponse> {
+        self.execute().await
+    }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<RemoveExportResponse> {
+        crate::blocking::block_on(self.send())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RemoveExportResponse {
+    /// Whether deletion was successful
+    pub delete: Option<bool>,
+    /// Whether cancellation was successful
+    pub cancel: Option<bool>,
+}
+
+impl Client {
+    pub fn remove_export(
+        &self,
+        id: impl Into<String>,
+        remove_type: RemoveExportType,
+    ) -> RemoveExportRequest {
+        RemoveExportRequest {
+            client: self.clone(),
+            id: id.into(),
+            remove_type,
+        }
+    }
+}
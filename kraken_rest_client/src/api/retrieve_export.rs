@@ -0,0 +1,329 @@
+use crate::{
+    api::{LedgerEntry, TradeInfo},
+    error::Error,
+    util::parse_comma_separated,
+    Client, OrderSide, OrderType, Result,
+};
+use chrono::NaiveDateTime;
+use serde::{de::DeserializeOwned, Deserialize};
+use std::io::{Cursor, Read};
+
+/// Retrieve a processed data export.
+///
+/// - <https://docs.kraken.com/rest/#tag/Account-Data/operation/retrieveExport>
+/// - <https://api.kraken.com/0/private/RetrieveExport>
+#[must_use = "Does nothing until you send or execute it"]
+pub struct RetrieveExportRequest {
+    client: Client,
+    /// Report ID to retrieve
+    id: String,
+}
+
+impl RetrieveExportRequest {
+    pub async fn execute(self) -> Result<Vec<u8>> {
+        let query = format!("id={}", self.id);
+
+        self.client
+            .send_private_bytes("/0/private/RetrieveExport", Some(query))
+            .await
+    }
+
+    pub async fn send(self) -> Result<ExportArchive> {
+        Ok(ExportArchive::new(self.execute().await?))
+    }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<ExportArchive> {
+        crate::blocking::block_on(self.send())
+    }
+}
+
+/// A zip archive containing an exported report.
+#[derive(Debug, Clone)]
+pub struct ExportArchive {
+    bytes: Vec<u8>,
+}
+
+impl ExportArchive {
+    pub fn new(bytes: Vec<u8>) -> Self {
+        Self { bytes }
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    /// Extracts the report file, returning its contents and field delimiter.
+    fn report(&self) -> Result<(Vec<u8>, u8)> {
+        let mut archive =
+            zip::ZipArchive::new(Cursor::new(&self.bytes)).map_err(Error::internal)?;
+
+        for i in 0..archive.len() {
+            let mut file = archive.by_index(i).map_err(Error::internal)?;
+
+            let delimiter = match file.name().rsplit('.').next() {
+                Some(ext) if ext.eq_ignore_ascii_case("csv") => b',',
+                Some(ext) if ext.eq_ignore_ascii_case("tsv") => b'\t',
+                _ => continue,
+            };
+
+            let mut contents = Vec::new();
+            file.read_to_end(&mut contents)?;
+
+            return Ok((contents, delimiter));
+        }
+
+        Err(Error::internal("no report file in export archive"))
+    }
+
+    fn records<R: DeserializeOwned>(&self) -> Result<Vec<R>> {
+        let (contents, delimiter) = self.report()?;
+
+        csv::ReaderBuilder::new()
+            .delimiter(delimiter)
+            .from_reader(contents.as_slice())
+            .deserialize()
+            .collect::<std::result::Result<_, _>>()
+            .map_err(Error::internal)
+    }
+
+    /// Parses a ledgers report into `(ledger id, entry)` pairs, in the order
+    /// of the report. Fields that were not selected in the export are empty.
+    pub fn ledgers(&self) -> Result<Vec<(String, LedgerEntry)>> {
+        self.records::<LedgerRecord>()?
+            .into_iter()
+            .map(LedgerRecord::into_entry)
+            .collect()
+    }
+
+    /// Parses a trades report into `(trade id, trade)` pairs, in the order of
+    /// the report. Fields that were not selected in the export are empty,
+    /// except `type` and `ordertype` which are required.
+    pub fn trades(&self) -> Result<Vec<(String, TradeInfo)>> {
+        self.records::<TradeRecord>()?
+            .into_iter()
+            .map(TradeRecord::into_trade)
+            .collect()
+    }
+}
+
+/// Parses a report time, either a unix timestamp or a UTC datetime like
+/// `2023-07-06 10:52:05.1234`.
+fn parse_time(time: Option<String>) -> Result<f64> {
+    let Some(time) = time.filter(|time| !time.is_empty()) else {
+        return Ok(0.0);
+    };
+
+    if let Ok(time) = time.parse::<f64>() {
+        return Ok(time);
+    }
+
+    let datetime = NaiveDateTime::parse_from_str(&time, "%Y-%m-%d %H:%M:%S%.f")
+        .map_err(|err| Error::internal(format!("invalid report time `{time}`: {err}")))?;
+
+    Ok(datetime.and_utc().timestamp_micros() as f64 / 1_000_000.0)
+}
+
+#[derive(Debug, Deserialize)]
+struct LedgerRecord {
+    txid: Option<String>,
+    refid: Option<String>,
+    time: Option<String>,
+    #[serde(rename = "type")]
+    ledger_type: Option<String>,
+    subtype: Option<String>,
+    aclass: Option<String>,
+    asset: Option<String>,
+    amount: Option<String>,
+    fee: Option<String>,
+    balance: Option<String>,
+}
+
+impl LedgerRecord {
+    fn into_entry(self) -> Result<(String, LedgerEntry)> {
+        let entry = LedgerEntry {
+            refid: self.refid.unwrap_or_default(),
+            time: parse_time(self.time)?,
+            ledger_type: self.ledger_type.unwrap_or_default(),
+            subtype: self.subtype.unwrap_or_default(),
+            aclass: self.aclass.unwrap_or_default(),
+            asset: self.asset.unwrap_or_default(),
+            amount: self.amount.unwrap_or_default(),
+            fee: self.fee.unwrap_or_default(),
+            balance: self.balance.unwrap_or_default(),
+        };
+
+        Ok((self.txid.unwrap_or_default(), entry))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct TradeRecord {
+    txid: Option<String>,
+    ordertxid: Option<String>,
+    postxid: Option<String>,
+    pair: Option<String>,
+    time: Option<String>,
+    #[serde(rename = "type")]
+    orderside: Option<String>,
+    ordertype: Option<String>,
+    price: Option<String>,
+    cost: Option<String>,
+    fee: Option<String>,
+    vol: Option<String>,
+    margin: Option<String>,
+    leverage: Option<String>,
+    misc: Option<String>,
+    ledgers: Option<String>,
+}
+
+impl TradeRecord {
+    fn into_trade(self) -> Result<(String, TradeInfo)> {
+        let required = |field: Option<String>, name: &str| {
+            field
+                .filter(|value| !value.is_empty())
+                .ok_or_else(|| Error::internal(format!("missing `{name}` in trades report")))
+        };
+
+        let orderside =
+            OrderSide::try_from(required(self.orderside, "type")?).map_err(Error::internal)?;
+        let ordertype =
+            OrderType::try_from(required(self.ordertype, "ordertype")?).map_err(Error::internal)?;
+
+        let trade = TradeInfo {
+            ordertxid: self.ordertxid.unwrap_or_default(),
+            postxid: self.postxid.filter(|postxid| !postxid.is_empty()),
+            pair: self.pair.unwrap_or_default(),
+            time: parse_time(self.time)?,
+            orderside,
+            ordertype,
+            price: self.price.unwrap_or_default(),
+            cost: self.cost.unwrap_or_default(),
+            fee: self.fee.unwrap_or_default(),
+            vol: self.vol.unwrap_or_default(),
+            margin: self.margin.unwrap_or_default(),
+            leverage: self.leverage.filter(|leverage| !leverage.is_empty()),
+            misc: parse_comma_separated(&self.misc.unwrap_or_default()),
+            ledgers: self
+                .ledgers
+                .filter(|ledgers| !ledgers.is_empty())
+                .map(|ledgers| ledgers.split(',').map(str::to_string).collect()),
+            trade_id: None,
+            maker: None,
+            posstatus: None,
+            cprice: None,
+            ccost: None,
+            cfee: None,
+            cvol: None,
+            cmargin: None,
+            net: None,
+            trades: None,
+        };
+
+        Ok((self.txid.unwrap_or_default(), trade))
+    }
+}
+
+impl Client {
+    pub fn retrieve_export(&self, id: impl Into<String>) -> RetrieveExportRequest {
+        RetrieveExportRequest {
+            client: self.clone(),
+            id: id.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ExportArchive;
+    use crate::{api::TradeMisc, OrderSide, OrderType};
+    use std::io::{Cursor, Write};
+    use zip::{write::SimpleFileOptions, CompressionMethod, ZipWriter};
+
+    fn archive(name: &str, contents: &str) -> ExportArchive {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+        writer.start_file(name, options).unwrap();
+        writer.write_all(contents.as_bytes()).unwrap();
+
+        ExportArchive::new(writer.finish().unwrap().into_inner())
+    }
+
+    #[test]
+    fn parse_ledgers_export() {
+        let archive = archive(
+            "ledgers.csv",
+            "\"txid\",\"refid\",\"time\",\"type\",\"subtype\",\"aclass\",\"asset\",\"wallet\",\"amount\",\"fee\",\"balance\"\n\
+             \"L4UESK-KG3EQ-UFO4T5\",\"TJKLXX-PGMUI-4NTLXU\",\"2023-07-06 10:52:05.5\",\"trade\",\"\",\"currency\",\"ZUSD\",\"spot / main\",-24.5000,0.0490,459567.9171\n",
+        );
+
+        let ledgers = archive.ledgers().unwrap();
+
+        assert_eq!(ledgers.len(), 1);
+
+        let (id, entry) = &ledgers[0];
+
+        assert_eq!(id, "L4UESK-KG3EQ-UFO4T5");
+        assert_eq!(entry.refid, "TJKLXX-PGMUI-4NTLXU");
+        assert_eq!(entry.time, 1688640725.5);
+        assert_eq!(entry.ledger_type, "trade");
+        assert_eq!(entry.amount, "-24.5000");
+        assert_eq!(entry.balance, "459567.9171");
+    }
+
+    #[test]
+    fn parse_trades_export_with_selected_fields() {
+        let archive = archive(
+            "trades.tsv",
+            "txid\tpair\ttime\ttype\tordertype\tprice\tvol\n\
+             TCWJEG-FL4SZ-3FKGH6\tXXBTZUSD\t1688667796.8802\tbuy\tlimit\t30010.00000\t0.00010000\n",
+        );
+
+        let trades = archive.trades().unwrap();
+
+        let (id, trade) = &trades[0];
+
+        assert_eq!(id, "TCWJEG-FL4SZ-3FKGH6");
+        assert_eq!(trade.pair, "XXBTZUSD");
+        assert_eq!(trade.time, 1688667796.8802);
+        assert_eq!(trade.orderside, OrderSide::Buy);
+        assert_eq!(trade.ordertype, OrderType::Limit);
+        assert_eq!(trade.cost, "");
+        assert!(trade.misc.is_empty());
+
+        let without_ordertype =
+            self::archive("trades.csv", "txid,pair,type\nTCWJEG,XXBTZUSD,buy\n");
+
+        assert!(without_ordertype.trades().is_err());
+    }
+
+    #[test]
+    fn parse_trades_export_lists() {
+        let archive = archive(
+            "trades.csv",
+            "txid,type,ordertype,misc,ledgers\n\
+             TCWJEG-FL4SZ-3FKGH6,sell,market,closing,\"L4UESK-KG3EQ-UFO4T5,LBPLBE-6CZRM-YKWR5P\"\n",
+        );
+
+        let trades = archive.trades().unwrap();
+
+        let (_, trade) = &trades[0];
+
+        assert_eq!(trade.orderside, OrderSide::Sell);
+        assert_eq!(trade.misc, vec![TradeMisc::Closing]);
+        assert_eq!(
+            trade.ledgers.as_deref(),
+            Some(
+                &[
+                    "L4UESK-KG3EQ-UFO4T5".to_string(),
+                    "LBPLBE-6CZRM-YKWR5P".to_string()
+                ][..]
+            )
+        );
+    }
+}
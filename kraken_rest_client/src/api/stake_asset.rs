@@ -29,6 +29,11 @@ impl StakeAssetRequest {
     pub async fn send(self) -> Result<StakeAssetResponse> {
         self.execute().await
     }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<StakeAssetResponse> {
+        crate::blocking::block_on(self.send())
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -37,6 +42,7 @@ pub struct StakeAssetResponse {
 }
 
 impl Client {
+    #[deprecated(note = "the legacy staking API is deprecated, use `allocate_earn_funds` instead")]
     pub fn stake_asset(
         &self,
         asset: impl Into<String>,
@@ -0,0 +1,498 @@
+//! Fixture tests for every endpoint, run against the mock server.
+
+use std::io::{Cursor, Write};
+
+use zip::{write::SimpleFileOptions, ZipWriter};
+
+use crate::api::{
+    AccountTransferStatus, AmendType, ExportReport, ExportState, OrderParams, RemoveExportType,
+};
+use crate::mock::MockKraken;
+use crate::{Error, FundingStatus, FundingStatusProp, OrderSide, OrderStatus, OrderType};
+
+#[tokio::test]
+async fn public_market_data() {
+    let mock = MockKraken::start().await;
+
+    for endpoint in [
+        "public/Time",
+        "public/SystemStatus",
+        "public/Assets",
+        "public/AssetPairs",
+        "public/Ticker",
+        "public/OHLC",
+        "public/Depth",
+        "public/Trades",
+        "public/Spread",
+    ] {
+        mock.serve(endpoint).await;
+    }
+
+    let client = mock.client();
+
+    let time = client.get_server_time().send().await.unwrap();
+    assert_eq!(time.unixtime, 1688669448);
+
+    let status = client.get_system_status().send().await.unwrap();
+    assert_eq!(status.status, "online");
+
+    let assets = client.get_assets().send().await.unwrap();
+    assert_eq!(assets["XXBT"].altname, "XBT");
+    assert_eq!(assets["ZUSD"].display_decimals, 2);
+
+    let pairs = client.get_asset_pairs().send().await.unwrap();
+    assert_eq!(pairs["XXBTZUSD"].wsname.as_deref(), Some("XBT/USD"));
+    assert_eq!(pairs["XXBTZUSD"].tick_size.as_deref(), Some("0.1"));
+
+    let tickers = client.get_tickers("XXBTZUSD").send().await.unwrap();
+    assert_eq!(tickers["XXBTZUSD"].o, "30502.80000");
+    assert_eq!(tickers["XXBTZUSD"].t, [34619, 38907]);
+
+    let ohlc = client.get_ohlc_data("XXBTZUSD").send().await.unwrap();
+    assert_eq!(ohlc.len(), 2);
+    assert_eq!(ohlc[0].close(), "30305.7");
+    assert_eq!(ohlc[0].count(), 23);
+
+    let book = client.get_order_book("XXBTZUSD").send().await.unwrap();
+    assert_eq!(book["XXBTZUSD"].asks[0].0, "30384.10000");
+    assert_eq!(book["XXBTZUSD"].bids.len(), 2);
+
+    let trades = client.get_recent_trades("XXBTZUSD").send().await.unwrap();
+    assert_eq!(trades.last, "1688671969993150842");
+    assert_eq!(trades.pair["XXBTZUSD"][1].buy_sell(), "s");
+    assert_eq!(trades.pair["XXBTZUSD"][1].trade_id(), 61044953);
+
+    let spreads = client.get_recent_spreads("XXBTZUSD").send().await.unwrap();
+    assert_eq!(spreads.last, 1688672106);
+    assert_eq!(spreads.pair["XXBTZUSD"][0].ask(), "30297.50000");
+}
+
+#[tokio::test]
+async fn account_data() {
+    let mock = MockKraken::start().await;
+
+    for endpoint in [
+        "private/Balance",
+        "private/TradeBalance",
+        "private/OpenOrders",
+        "private/ClosedOrders",
+        "private/QueryOrders",
+        "private/TradesHistory",
+        "private/OpenPositions",
+        "private/Ledgers",
+        "private/TradeVolume",
+        "private/GetWebSocketsToken",
+    ] {
+        mock.serve(endpoint).await;
+    }
+
+    let client = mock.client();
+
+    let balance = client.get_account_balance().send().await.unwrap();
+    assert_eq!(balance["ZUSD"], "171288.6158");
+
+    let trade_balance = client.get_trade_balance().send().await.unwrap();
+    assert_eq!(trade_balance.equivalent_balance, "1101.3425");
+    assert_eq!(trade_balance.margin_level.as_deref(), Some("5432.57"));
+
+    let open = client.get_open_orders().send().await.unwrap();
+    let order = &open.open["OQCLML-BW3P3-BUCMWZ"];
+    assert_eq!(order.descr.ordertype, OrderType::Limit);
+    assert_eq!(order.descr.orderside, OrderSide::Buy);
+    assert_eq!(order.vol_exec, "0.37500000");
+    assert_eq!(order.trades.as_ref().unwrap(), &["TCCCTY-WE2O6-P3NB37"]);
+
+    let closed = client.get_closed_orders().send().await.unwrap();
+    assert_eq!(closed.count, 1);
+    let order = &closed.closed["O37652-RJWRT-IMO74O"];
+    assert_eq!(order.status, OrderStatus::Canceled);
+    assert_eq!(order.reason.as_deref(), Some("User requested"));
+
+    let orders = client
+        .query_orders_info("OBCMZD-JIEE7-77TH3F")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(orders["OBCMZD-JIEE7-77TH3F"].cost, "27526.2");
+
+    let trades = client.get_trades_history().send().await.unwrap();
+    assert_eq!(trades.count, 1);
+    let trade = &trades.trades["THVRQM-33VKH-UCI7BS"];
+    assert_eq!(trade.price, "30010.00000");
+    assert_eq!(trade.orderside, OrderSide::Buy);
+    assert_eq!(trade.trade_id, Some(40274859));
+    assert_eq!(trade.maker, Some(true));
+
+    let positions = client.get_open_positions().send().await.unwrap();
+    assert_eq!(positions["TF5GVO-T7ZZ2-6NBKBI"].vol_closed, "0.20200000");
+
+    let ledgers = client.get_ledgers().send().await.unwrap();
+    assert_eq!(ledgers.count, 2);
+    assert_eq!(ledgers.ledger["L4UESK-KG3EQ-UFO4T5"].fee, "0.0490");
+
+    let volume = client.get_trade_volume("XXBTZUSD").send().await.unwrap();
+    assert_eq!(volume.volume, "200709587.4223");
+    assert!(volume.fees_maker.contains_key("XXBTZUSD"));
+
+    let token = client.get_web_sockets_token().send().await.unwrap();
+    assert_eq!(token.expires, 900);
+}
+
+#[tokio::test]
+async fn trading() {
+    let mock = MockKraken::start().await;
+
+    for endpoint in [
+        "private/AddOrder",
+        "private/AddOrderBatch",
+        "private/EditOrder",
+        "private/AmendOrder",
+        "private/OrderAmends",
+        "private/CancelOrder",
+        "private/CancelAll",
+    ] {
+        mock.serve(endpoint).await;
+    }
+
+    let client = mock.client();
+
+    let order = client
+        .add_limit_order("XBTUSD", OrderSide::Buy, "1.25", "27500.0")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(order.txid.unwrap(), ["OU22CG-KLAF2-FWUDD7"]);
+
+    let batch = client
+        .add_order_batch(
+            "XBTUSD",
+            vec![
+                OrderParams::limit(OrderSide::Buy, "1.0201", "29000.0"),
+                OrderParams::limit(OrderSide::Buy, "-1", "29000.0"),
+            ],
+        )
+        .send()
+        .await
+        .unwrap();
+    let results = batch.results();
+    assert_eq!(
+        results[0].as_ref().unwrap().as_deref(),
+        Some("65LRD3-AHGRA-YAH8V5")
+    );
+    assert!(matches!(results[1], Err(Error::Api(_))));
+
+    let edit = client
+        .edit_order("OHYO67-6LP66-HMQ437", "XXBTZGBP")
+        .volume("0.0003")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(edit.txid.as_deref(), Some("OFVXHJ-KPQ3B-VS7ELA"));
+    assert_eq!(edit.orders_cancelled, Some(1));
+
+    let amend = client
+        .amend_order("OQCLML-BW3P3-BUCMWZ")
+        .order_qty("1.0")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(amend.amend_id, "TTW6PD-RC36L-ZZSWNU");
+
+    let amends = client
+        .get_order_amends("OQCLML-BW3P3-BUCMWZ")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(amends.count, 2);
+    assert_eq!(amends.amends[1].amend_type, AmendType::User);
+
+    let cancel = client
+        .cancel_order("OQCLML-BW3P3-BUCMWZ")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(cancel.count, 1);
+
+    let cancel = client
+        .cancel_order_batch(vec!["OQCLML-BW3P3-BUCMWZ".to_string()])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(cancel.count, 1);
+
+    let cancel = client.cancel_all_orders().send().await.unwrap();
+    assert_eq!(cancel.count, 4);
+}
+
+#[tokio::test]
+async fn funding() {
+    let mock = MockKraken::start().await;
+
+    for endpoint in [
+        "private/DepositMethods",
+        "private/DepositAddresses",
+        "private/DepositStatus",
+        "private/WithdrawMethods",
+        "private/WithdrawAddresses",
+        "private/WithdrawInfo",
+        "private/Withdraw",
+        "private/WithdrawStatus",
+        "private/WithdrawCancel",
+        "private/WalletTransfer",
+    ] {
+        mock.serve(endpoint).await;
+    }
+
+    let client = mock.client();
+
+    let methods = client.get_deposit_methods("XBT").send().await.unwrap();
+    assert_eq!(methods[0].gen_address, Some(true));
+    assert_eq!(methods[1].gen_address, None);
+
+    let addresses = client
+        .get_deposit_addresses("XBT", "Bitcoin")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(addresses.len(), 2);
+    assert_eq!(addresses[0].new, Some(true));
+
+    let deposits = client.get_deposit_status("XBT").send().await.unwrap();
+    assert_eq!(deposits[0].status, FundingStatus::Success);
+    assert_eq!(deposits[0].status_prop, Some(FundingStatusProp::Return));
+
+    let methods = client.get_withdrawal_methods().send().await.unwrap();
+    assert_eq!(methods[1].network, "Lightning");
+
+    let addresses = client.get_withdrawal_addresses().send().await.unwrap();
+    assert!(addresses[0].verified);
+
+    let info = client
+        .get_withdrawal_info("XBT", "btc-wallet-1", "0.725")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(info.fee, "0.00015000");
+
+    let withdrawal = client
+        .withdraw("XBT", "btc-wallet-1", "0.725")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(withdrawal.refid, "FTQcuak-V6Za8qrWnhzTx67yYHz8Tg");
+
+    let withdrawals = client.get_withdrawal_status().send().await.unwrap();
+    assert_eq!(withdrawals[0].status, FundingStatus::Pending);
+    assert_eq!(withdrawals[0].key.as_deref(), Some("btc-wallet-1"));
+
+    let canceled = client
+        .cancel_withdrawal("XBT", "FTQcuak-V6Za8qrWnhzTx67yYHz8Tg")
+        .send()
+        .await
+        .unwrap();
+    assert!(canceled);
+
+    let transfer = client
+        .transfer_to_futures("XBT", "0.1")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(transfer.refid, "BOG5AE5-KSCNR4-VPNPEV");
+}
+
+#[tokio::test]
+async fn subaccounts() {
+    let mock = MockKraken::start().await;
+
+    mock.serve("private/CreateSubaccount").await;
+    mock.serve("private/AccountTransfer").await;
+
+    let client = mock.client();
+
+    let created = client
+        .create_subaccount("trader-1", "trader-1@example.com")
+        .send()
+        .await
+        .unwrap();
+    assert!(created);
+
+    let transfer = client
+        .account_transfer("XBT", "1.0", "AA25 N84G 6KFB 4ZQP", "AA72 N84G 6KFB 4ZQP")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(transfer.status, AccountTransferStatus::Complete);
+
+    let request = &mock.requests().await[1];
+    assert!(String::from_utf8_lossy(&request.body).starts_with(
+        "asset=XBT&amount=1.0&from=AA25%20N84G%206KFB%204ZQP&to=AA72%20N84G%206KFB%204ZQP&nonce="
+    ));
+}
+
+#[tokio::test]
+async fn exports() {
+    let mock = MockKraken::start().await;
+
+    mock.serve("private/AddExport").await;
+    mock.serve("private/ExportStatus").await;
+    mock.serve("private/RemoveExport").await;
+
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    writer
+        .start_file("trades.csv", SimpleFileOptions::default())
+        .unwrap();
+    writer
+        .write_all(b"txid,pair,time,type,ordertype,price,vol\nTCWJEG-FL4SZ-3FKGH6,XXBTZUSD,1688667796.8802,buy,limit,30010.00000,0.00010000\n")
+        .unwrap();
+    let archive = writer.finish().unwrap().into_inner();
+
+    mock.serve_body("private/RetrieveExport", archive, "application/zip")
+        .await;
+
+    let client = mock.client();
+
+    let export = client
+        .add_export(ExportReport::Trades, "my_trades_1")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(export.id, "TCJA");
+
+    let status = client
+        .get_export_status(ExportReport::Trades)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(status[0].status, ExportState::Processed);
+
+    let archive = client.retrieve_export("VSKC").send().await.unwrap();
+    assert_eq!(archive.trades().unwrap()[0].1.pair, "XXBTZUSD");
+
+    let removed = client
+        .remove_export("VSKC", RemoveExportType::Delete)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(removed.delete, Some(true));
+}
+
+#[tokio::test]
+#[allow(deprecated)]
+async fn earn_and_staking() {
+    let mock = MockKraken::start().await;
+
+    for endpoint in [
+        "private/Earn/Strategies",
+        "private/Earn/Allocations",
+        "private/Earn/Allocate",
+        "private/Earn/Deallocate",
+        "private/Earn/AllocateStatus",
+        "private/Earn/DeallocateStatus",
+        "private/Staking/Assets",
+        "private/Stake",
+        "private/Unstake",
+    ] {
+        mock.serve(endpoint).await;
+    }
+
+    let client = mock.client();
+
+    let strategies = client.list_earn_strategies().send().await.unwrap();
+    assert_eq!(strategies.items[0].id, "ESRFUO3-Q62XD-WIOIL7");
+
+    let allocations = client.list_earn_allocations().send().await.unwrap();
+    assert_eq!(allocations.total_allocated, "49.2398");
+
+    let strategy_id = "ESRFUO3-Q62XD-WIOIL7";
+
+    assert!(client
+        .allocate_earn_funds(strategy_id, "1.0")
+        .send()
+        .await
+        .unwrap());
+    assert!(client
+        .deallocate_earn_funds(strategy_id, "1.0")
+        .send()
+        .await
+        .unwrap());
+
+    let status = client
+        .get_earn_allocation_status(strategy_id)
+        .send()
+        .await
+        .unwrap();
+    assert!(status.pending);
+
+    let status = client
+        .get_earn_deallocation_status(strategy_id)
+        .send()
+        .await
+        .unwrap();
+    assert!(!status.pending);
+
+    let assets = client.get_stakeable_assets().send().await.unwrap();
+    assert_eq!(assets[0].staking_asset, "ETH2");
+
+    let stake = client
+        .stake_asset("XETH", "0.1", "ethereum-staked")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(stake.refid, "BOG5AE5-KSCNR4-VPNPEV");
+
+    let unstake = client.unstake_asset("ETH2", "0.1").send().await.unwrap();
+    assert_eq!(unstake.refid, "BOG5AE5-KSCNR4-VPNPEV");
+}
+
+#[tokio::test]
+async fn private_requests_are_signed() {
+    let mock = MockKraken::start().await;
+
+    mock.serve("private/Balance").await;
+
+    assert!(mock.client().get_account_balance().send().await.is_ok());
+
+    let request = &mock.requests().await[0];
+    assert!(String::from_utf8_lossy(&request.body).starts_with("nonce="));
+
+    let client = crate::Client::builder()
+        .base_url(&mock.uri())
+        .auth(crate::mock::API_KEY, "d3Jvbmctc2VjcmV0")
+        .build();
+
+    assert_eq!(
+        client.get_account_balance().send().await.unwrap_err(),
+        Error::Api("EAPI:Invalid signature".into())
+    );
+}
+
+#[tokio::test]
+async fn errors_and_rate_limits() {
+    let mock = MockKraken::start().await;
+
+    mock.api_error("private/AddOrder", "EOrder:Insufficient funds")
+        .await;
+    mock.rate_limit("private/Balance").await;
+    mock.http_error("public/Time", 503).await;
+
+    let client = mock.client();
+
+    let err = client
+        .add_market_order("XBTUSD", OrderSide::Buy, "100")
+        .send()
+        .await
+        .unwrap_err();
+    assert_eq!(err, Error::Api("EOrder:Insufficient funds".into()));
+
+    let err = client.get_account_balance().send().await.unwrap_err();
+    assert!(err.is_rate_limit());
+
+    let err = client.get_server_time().send().await.unwrap_err();
+    assert!(matches!(
+        err,
+        Error::FailedRequest {
+            status: Some(503),
+            ..
+        }
+    ));
+}
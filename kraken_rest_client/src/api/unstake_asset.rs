@@ -24,6 +24,11 @@ impl UnstakeAssetRequest {
     pub async fn send(self) -> Result<UnstakeAssetResponse> {
         self.execute().await
     }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<UnstakeAssetResponse> {
+        crate::blocking::block_on(self.send())
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -32,6 +37,9 @@ pub struct UnstakeAssetResponse {
 }
 
 impl Client {
+    #[deprecated(
+        note = "the legacy staking API is deprecated, use `deallocate_earn_funds` instead"
+    )]
     pub fn unstake_asset(&self, asset: impl Into<String>, amount: &str) -> UnstakeAssetRequest {
         UnstakeAssetRequest {
             client: self.clone(),
@@ -0,0 +1,93 @@
+use crate::{Client, Result};
+use serde::{de::DeserializeOwned, Deserialize};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Wallet {
+    Spot,
+    Futures,
+}
+
+impl fmt::Display for Wallet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let wallet = match self {
+            Self::Spot => "Spot Wallet",
+            Self::Futures => "Futures Wallet",
+        };
+
+        write!(f, "{}", wallet)
+    }
+}
+
+/// Transfer from a Kraken spot wallet to a Kraken Futures wallet. Note that a
+/// transfer in the other direction must be requested via the Kraken Futures
+/// API.
+///
+/// - <https://docs.kraken.com/rest/#tag/Funding/operation/walletTransfer>
+/// - <https://api.kraken.com/0/private/WalletTransfer>
+#[must_use = "Does nothing until you send or execute it"]
+pub struct WalletTransferRequest {
+    client: Client,
+    /// Asset to transfer (asset ID or altname)
+    asset: String,
+    /// Source wallet
+    from: Wallet,
+    /// Destination wallet
+    to: Wallet,
+    /// Amount to transfer
+    amount: String,
+}
+
+impl WalletTransferRequest {
+    pub async fn execute<T: DeserializeOwned>(self) -> Result<T> {
+        let query = format!(
+            "asset={}&from={}&to={}&amount={}",
+            self.asset,
+            urlencoding::encode(&self.from.to_string()),
+            urlencoding::encode(&self.to.to_string()),
+            self.amount
+        );
+
+        self.client
+            .send_private("/0/private/WalletTransfer", Some(query))
+            .await
+    }
+
+    pub async fn send(self) -> Result<WalletTransferResponse> {
+        self.execute().await
+    }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<WalletTransferResponse> {
+        crate::blocking::block_on(self.send())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WalletTransferResponse {
+    /// Reference ID
+    pub refid: String,
+}
+
+impl Client {
+    pub fn wallet_transfer(
+        &self,
+        asset: &str,
+        from: Wallet,
+        to: Wallet,
+        amount: &str,
+    ) -> WalletTransferRequest {
+        WalletTransferRequest {
+            client: self.clone(),
+            asset: asset.to_string(),
+            from,
+            to,
+            amount: amount.to_string(),
+        }
+    }
+
+    /// Transfers funds from the spot wallet to the futures wallet.
+    pub fn transfer_to_futures(&self, asset: &str, amount: &str) -> WalletTransferRequest {
+        self.wallet_transfer(asset, Wallet::Spot, Wallet::Futures, amount)
+    }
+}
@@ -1,5 +1,6 @@
-use crate::{Client, Result};
+use crate::{api::WithdrawalStatus, Client, Result};
 use serde::{de::DeserializeOwned, Deserialize};
+use std::time::Duration;
 
 /// - <https://docs.kraken.com/rest/#tag/Funding/operation/withdrawFunds>
 /// - <https://api.kraken.com/0/private/Withdraw>
@@ -41,6 +42,24 @@ impl WithdrawRequest {
     pub async fn send(self) -> Result<WithdrawResponse> {
         self.execute().await
     }
+
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(self) -> Result<WithdrawResponse> {
+        crate::blocking::block_on(self.send())
+    }
+
+    /// Sends the withdrawal request and polls its status every
+    /// `poll_interval` until it succeeds, fails or is canceled.
+    pub async fn send_and_wait(self, poll_interval: Duration) -> Result<WithdrawalStatus> {
+        let client = self.client.clone();
+        let asset = self.asset.clone();
+
+        let WithdrawResponse { refid } = self.send().await?;
+
+        client
+            .wait_for_withdrawal(&asset, &refid, poll_interval)
+            .await
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -0,0 +1,482 @@
+//! Utilities to backfill historical market data.
+//!
+//! The `OHLC` endpoint only returns the most recent 720 candles and the
+//! `Trades` endpoint returns one batch per call. The helpers in this module
+//! walk the `since`/`last` cursors to fetch the complete trade history of a
+//! pair over a date range, aggregate trades into candles of any `Interval`, and
+//! write the results incrementally to CSV files.
+
+use std::collections::VecDeque;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::Duration;
+
+use futures::{stream, Stream, StreamExt};
+use rust_decimal::Decimal;
+
+use crate::{
+    api::{GetRecentTradesResponse, Trade, OHLC},
+    error::Error,
+    paginate::retry_rate_limited,
+    Client, Interval, Result,
+};
+
+/// The default pause between consecutive requests to the public endpoints,
+/// which allow roughly one call per second.
+pub const DEFAULT_BACKFILL_DELAY: Duration = Duration::from_secs(1);
+
+/// The maximum number of trades returned by a single `Trades` call.
+const MAX_TRADES_COUNT: u16 = 1000;
+
+struct TradesState {
+    client: Client,
+    pair: String,
+    cursor: String,
+    end: i64,
+    delay: Duration,
+    buffer: VecDeque<Trade>,
+    started: bool,
+    done: bool,
+}
+
+impl Client {
+    /// Returns a stream of all trades of `pair` with `start <= time < end`
+    /// (unix timestamps in seconds), in chronological order.
+    pub fn backfill_trades(
+        &self,
+        pair: impl Into<String>,
+        start: i64,
+        end: i64,
+    ) -> impl Stream<Item = Result<Trade>> {
+        self.backfill_trades_with_delay(pair, start, end, DEFAULT_BACKFILL_DELAY)
+    }
+
+    /// Like `backfill_trades`, with a custom pause between requests.
+    pub fn backfill_trades_with_delay(
+        &self,
+        pair: impl Into<String>,
+        start: i64,
+        end: i64,
+        delay: Duration,
+    ) -> impl Stream<Item = Result<Trade>> {
+        let state = TradesState {
+            client: self.clone(),
+            pair: pair.into(),
+            cursor: start.to_string(),
+            end,
+            delay,
+            buffer: VecDeque::new(),
+            started: false,
+            done: false,
+        };
+
+        stream::unfold(state, |mut state| async move {
+            loop {
+                if let Some(trade) = state.buffer.pop_front() {
+                    return Some((Ok(trade), state));
+                }
+
+                if state.done {
+                    return None;
+                }
+
+                if state.started && !state.delay.is_zero() {
+                    tokio::time::sleep(state.delay).await;
+                }
+
+                state.started = true;
+
+                let resp = retry_rate_limited(
+                    || {
+                        state
+                            .client
+                            .get_recent_trades(&state.pair)
+                            .count(MAX_TRADES_COUNT)
+                            .since(state.cursor.clone())
+                            .send()
+                    },
+                    state.delay,
+                )
+                .await;
+
+                let GetRecentTradesResponse { last, pair } = match resp {
+                    Ok(resp) => resp,
+                    Err(error) => {
+                        state.done = true;
+                        return Some((Err(error), state));
+                    }
+                };
+
+                // The response is keyed by the canonical pair name, which may
+                // differ from the requested one.
+                let trades = pair.into_values().next().unwrap_or_default();
+
+                if trades.is_empty() || last == state.cursor {
+                    state.done = true;
+                }
+
+                for trade in trades {
+                    if trade.time() >= state.end as f64 {
+                        state.done = true;
+                        break;
+                    }
+                    state.buffer.push_back(trade);
+                }
+
+                state.cursor = last;
+            }
+        })
+    }
+
+    /// Fetches the candles of `pair` with `start <= time < end`.
+    ///
+    /// Candles are fetched from the `OHLC` endpoint. The part of the range that
+    /// the endpoint cannot reach is built from the trade history. A `start`
+    /// inside an interval is rounded up to the next candle, as the candle
+    /// containing it starts earlier.
+    pub async fn backfill_ohlc(
+        &self,
+        pair: impl Into<String>,
+        interval: Interval,
+        start: i64,
+        end: i64,
+    ) -> Result<Vec<OHLC>> {
+        let pair = pair.into();
+
+        let seconds = interval.seconds();
+        let start = (start + seconds - 1).div_euclid(seconds) * seconds;
+
+        let candles = self
+            .get_ohlc_data(&pair)
+            .interval(interval)
+            .since(start)
+            .send()
+            .await?;
+
+        let mut candles: Vec<OHLC> = candles
+            .into_iter()
+            .filter(|candle| candle.time() >= start && candle.time() < end)
+            .collect();
+
+        let covered_from = candles.first().map_or(end, OHLC::time);
+
+        if covered_from > start {
+            let mut builder = CandleBuilder::new(interval);
+            let mut built = Vec::new();
+
+            let trades = self.backfill_trades(&pair, start, covered_from);
+            futures::pin_mut!(trades);
+
+            while let Some(trade) = trades.next().await {
+                if let Some(candle) = builder.push(&trade?)? {
+                    built.push(candle);
+                }
+            }
+
+            built.extend(builder.finish());
+            built.append(&mut candles);
+            candles = built;
+        }
+
+        Ok(candles)
+    }
+
+    /// Writes all trades of `pair` with `start <= time < end` to a CSV file,
+    /// as they are fetched. Returns the number of trades written.
+    pub async fn backfill_trades_to_csv(
+        &self,
+        pair: impl Into<String>,
+        start: i64,
+        end: i64,
+        path: impl AsRef<Path>,
+    ) -> Result<usize> {
+        let mut writer = csv::Writer::from_path(path).map_err(Error::internal)?;
+        let mut written = 0;
+
+        writer
+            .write_record(Trade::header())
+            .map_err(Error::internal)?;
+
+        let trades = self.backfill_trades(pair, start, end);
+        futures::pin_mut!(trades);
+
+        while let Some(trade) = trades.next().await {
+            writer
+                .write_record(trade?.fields())
+                .map_err(Error::internal)?;
+            written += 1;
+        }
+
+        writer.flush()?;
+
+        Ok(written)
+    }
+}
+
+fn parse_decimal(value: &str) -> Result<Decimal> {
+    Decimal::from_str(value)
+        .map_err(|err| Error::internal(format!("invalid decimal `{value}`: {err}")))
+}
+
+struct PartialCandle {
+    time: i64,
+    open: Decimal,
+    high: Decimal,
+    low: Decimal,
+    close: Decimal,
+    volume: Decimal,
+    notional: Decimal,
+    price_scale: u32,
+    count: u64,
+}
+
+impl PartialCandle {
+    fn into_ohlc(self) -> OHLC {
+        let vwap = if self.volume.is_zero() {
+            self.close
+        } else {
+            (self.notional / self.volume).round_dp(self.price_scale)
+        };
+
+        OHLC(
+            self.time,
+            self.open.to_string(),
+            self.high.to_string(),
+            self.low.to_string(),
+            self.close.to_string(),
+            vwap.to_string(),
+            self.volume.to_string(),
+            self.count,
+        )
+    }
+}
+
+/// Aggregates chronologically ordered trades into candles.
+///
+/// Intervals without trades produce no candle.
+pub struct CandleBuilder {
+    interval: Interval,
+    current: Option<PartialCandle>,
+}
+
+impl CandleBuilder {
+    pub fn new(interval: Interval) -> Self {
+        Self {
+            interval,
+            current: None,
+        }
+    }
+
+    /// Adds a trade, returning the previous candle if the trade starts a new
+    /// one.
+    pub fn push(&mut self, trade: &Trade) -> Result<Option<OHLC>> {
+        let price = parse_decimal(trade.price())?;
+        let volume = parse_decimal(trade.volume())?;
+
+        let seconds = self.interval.seconds();
+        let time = (trade.time() as i64).div_euclid(seconds) * seconds;
+
+        if let Some(candle) = &mut self.current {
+            if candle.time == time {
+                candle.high = candle.high.max(price);
+                candle.low = candle.low.min(price);
+                candle.close = price;
+                candle.volume += volume;
+                candle.notional += price * volume;
+                candle.price_scale = candle.price_scale.max(price.scale());
+                candle.count += 1;
+                return Ok(None);
+            }
+        }
+
+        let completed = self.current.replace(PartialCandle {
+            time,
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+            volume,
+            notional: price * volume,
+            price_scale: price.scale(),
+            count: 1,
+        });
+
+        Ok(completed.map(PartialCandle::into_ohlc))
+    }
+
+    /// Returns the last, possibly incomplete, candle.
+    pub fn finish(self) -> Option<OHLC> {
+        self.current.map(PartialCandle::into_ohlc)
+    }
+}
+
+/// Aggregates chronologically ordered trades into candles.
+pub fn build_candles<'a>(
+    trades: impl IntoIterator<Item = &'a Trade>,
+    interval: Interval,
+) -> Result<Vec<OHLC>> {
+    let mut builder = CandleBuilder::new(interval);
+    let mut candles = Vec::new();
+
+    for trade in trades {
+        if let Some(candle) = builder.push(trade)? {
+            candles.push(candle);
+        }
+    }
+
+    candles.extend(builder.finish());
+
+    Ok(candles)
+}
+
+/// A record that can be written as a CSV row, e.g. with `csv::Writer`.
+///
+/// The columns are flat and consistently typed, so the files can be loaded
+/// directly by dataframe tools.
+pub trait CsvRecord {
+    fn header() -> &'static [&'static str];
+
+    fn fields(&self) -> Vec<String>;
+}
+
+impl CsvRecord for Trade {
+    fn header() -> &'static [&'static str] {
+        &[
+            "trade_id",
+            "time",
+            "price",
+            "volume",
+            "side",
+            "order_type",
+            "misc",
+        ]
+    }
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            self.trade_id().to_string(),
+            self.time().to_string(),
+            self.price().to_string(),
+            self.volume().to_string(),
+            self.buy_sell().to_string(),
+            self.market_limit().to_string(),
+            self.miscellaneous().to_string(),
+        ]
+    }
+}
+
+impl CsvRecord for OHLC {
+    fn header() -> &'static [&'static str] {
+        &[
+            "time", "open", "high", "low", "close", "vwap", "volume", "count",
+        ]
+    }
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            self.time().to_string(),
+            self.open().clone(),
+            self.high().clone(),
+            self.low().clone(),
+            self.close().clone(),
+            self.vwap().clone(),
+            self.volume().clone(),
+            self.count().to_string(),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build_candles, CsvRecord};
+    use crate::{api::Trade, mock::MockKraken, Interval};
+
+    fn trade(price: &str, volume: &str, time: f64, trade_id: u64) -> Trade {
+        Trade(
+            price.into(),
+            volume.into(),
+            time,
+            "b".into(),
+            "l".into(),
+            "".into(),
+            trade_id,
+        )
+    }
+
+    #[test]
+    fn build_candles_aggregates_trades_per_interval() {
+        let trades = vec![
+            trade("100.0", "1", 60.5, 1),
+            trade("102.5", "3", 61.0, 2),
+            trade("99.0", "1", 119.9, 3),
+            trade("101.0", "2", 240.0, 4),
+        ];
+
+        let candles = build_candles(&trades, Interval::Min1).unwrap();
+
+        assert_eq!(candles.len(), 2);
+
+        assert_eq!(candles[0].time(), 60);
+        assert_eq!(candles[0].open(), "100.0");
+        assert_eq!(candles[0].high(), "102.5");
+        assert_eq!(candles[0].low(), "99.0");
+        assert_eq!(candles[0].close(), "99.0");
+        assert_eq!(candles[0].vwap(), "101.3");
+        assert_eq!(candles[0].volume(), "5");
+        assert_eq!(candles[0].count(), 3);
+
+        assert_eq!(candles[1].time(), 240);
+        assert_eq!(candles[1].count(), 1);
+    }
+
+    #[test]
+    fn csv_records_include_a_header() {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+
+        writer.write_record(Trade::header()).unwrap();
+        writer
+            .write_record(trade("100.0", "1", 60.5, 1).fields())
+            .unwrap();
+        writer
+            .write_record(trade("101.0", "2", 61.5, 2).fields())
+            .unwrap();
+
+        let csv = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+
+        assert_eq!(
+            csv,
+            "trade_id,time,price,volume,side,order_type,misc\n\
+             1,60.5,100.0,1,b,l,\n\
+             2,61.5,101.0,2,b,l,\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn backfill_ohlc_rounds_start_up_to_the_next_candle() {
+        let mock = MockKraken::start().await;
+        mock.serve("public/OHLC").await;
+        mock.serve("public/Trades").await;
+
+        mock.client()
+            .backfill_ohlc("XXBTZUSD", Interval::Min1, 1688669597, 1688671320)
+            .await
+            .unwrap();
+
+        let requests = mock.requests().await;
+        let since = |path: &str| {
+            requests
+                .iter()
+                .find(|request| request.url.path() == path)
+                .and_then(|request| {
+                    request
+                        .url
+                        .query_pairs()
+                        .find(|(key, _)| key == "since")
+                        .map(|(_, value)| value.into_owned())
+                })
+        };
+
+        assert_eq!(since("/0/public/OHLC").as_deref(), Some("1688669640"));
+        assert_eq!(since("/0/public/Trades").as_deref(), Some("1688669640"));
+    }
+}
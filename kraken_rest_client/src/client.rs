@@ -1,10 +1,20 @@
-use std::time::Duration;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
+use kraken_common::validate_base_url;
 use reqwest::header;
 use serde::{de::DeserializeOwned, Deserialize};
 use serde_json::Value;
 
-use crate::error::Error;
+use crate::clock::{Clock, ClockSkew, SkewCallback, DEFAULT_MAX_CLOCK_SKEW};
+use crate::credentials::{
+    Credentials, CredentialsCallback, CredentialsSource, SecretString, SigningKey,
+};
+use crate::error::{BuildError, Error};
+use crate::interceptor::{Interceptor, Interceptors, RequestInfo, ResponseInfo};
+use crate::otp::{Otp, Totp};
+use crate::raw::{decode_str, RawCapture, RawResponse};
 use crate::sign;
 
 #[derive(Debug, Deserialize)]
@@ -26,9 +36,15 @@ pub struct ClientBuilder {
     base_url: Option<String>,
     user_agent: Option<String>,
     api_key: Option<String>,
-    api_secret: Option<String>,
+    api_secret: Option<SecretString>,
+    credentials_callback: Option<CredentialsCallback>,
     http_client: Option<reqwest::Client>,
     timeout: Option<Duration>,
+    otp: Option<Otp>,
+    interceptors: Interceptors,
+    sync_clock: bool,
+    max_clock_skew: Option<Duration>,
+    on_clock_skew: Option<SkewCallback>,
 }
 
 impl ClientBuilder {
@@ -47,17 +63,31 @@ impl ClientBuilder {
         self
     }
 
-    pub fn api_secret(mut self, api_secret: impl Into<String>) -> Self {
+    pub fn api_secret(mut self, api_secret: impl Into<SecretString>) -> Self {
         self.api_secret = Some(api_secret.into());
         self
     }
 
-    pub fn auth(mut self, api_key: impl Into<String>, api_secret: impl Into<String>) -> Self {
+    pub fn auth(mut self, api_key: impl Into<String>, api_secret: impl Into<SecretString>) -> Self {
         self.api_key = Some(api_key.into());
         self.api_secret = Some(api_secret.into());
         self
     }
 
+    pub fn credentials(self, credentials: Credentials) -> Self {
+        self.auth(credentials.api_key(), credentials.api_secret().clone())
+    }
+
+    /// Calls `callback` to get the credentials for every signed request, so
+    /// that rotated keys are picked up without rebuilding the client.
+    pub fn credentials_callback(
+        mut self,
+        callback: impl Fn() -> Result<Credentials> + Send + Sync + 'static,
+    ) -> Self {
+        self.credentials_callback = Some(Arc::new(callback));
+        self
+    }
+
     pub fn http_client(mut self, http_client: reqwest::Client) -> Self {
         self.http_client = Some(http_client);
         self
@@ -68,23 +98,155 @@ impl ClientBuilder {
         self
     }
 
-    pub fn build(self) -> Client {
-        // #todo handle the unwrap
+    /// A static two-factor password, sent with every private request.
+    pub fn otp(mut self, otp: impl Into<String>) -> Self {
+        self.otp = Some(Otp::Static(otp.into()));
+        self
+    }
+
+    /// Generates a fresh TOTP code for every private request.
+    pub fn totp(mut self, totp: Totp) -> Self {
+        self.otp = Some(Otp::Totp(totp));
+        self
+    }
+
+    /// Calls `callback` to get the two-factor password for every private
+    /// request.
+    pub fn otp_callback(mut self, callback: impl Fn() -> String + Send + Sync + 'static) -> Self {
+        self.otp = Some(Otp::Callback(Arc::new(callback)));
+        self
+    }
+
+    /// Measures the offset to the server clock before the first signed
+    /// request, and aligns nonces and deadlines with the server clock.
+    pub fn sync_clock(mut self, sync_clock: bool) -> Self {
+        self.sync_clock = sync_clock;
+        self
+    }
+
+    /// The clock skew above which `on_clock_skew` is called, 1 second by
+    /// default.
+    pub fn max_clock_skew(mut self, max_clock_skew: Duration) -> Self {
+        self.max_clock_skew = Some(max_clock_skew);
+        self
+    }
+
+    /// Calls `callback` when the measured clock skew exceeds
+    /// `max_clock_skew`.
+    pub fn on_clock_skew(mut self, callback: impl Fn(&ClockSkew) + Send + Sync + 'static) -> Self {
+        self.on_clock_skew = Some(Arc::new(callback));
+        self
+    }
+
+    /// Adds an interceptor that observes, and may veto, every call.
+    pub fn interceptor(mut self, interceptor: impl Interceptor + 'static) -> Self {
+        self.interceptors.push(Arc::new(interceptor));
+        self
+    }
+
+    /// Validates the configuration and builds the client, decoding the API
+    /// secret once.
+    pub fn try_build(mut self) -> std::result::Result<Client, BuildError> {
+        let base_url = self.base_url.as_deref().unwrap_or(DEFAULT_BASE_URL);
+        let base_url = validate_base_url(base_url)
+            .map_err(|reason| BuildError::InvalidBaseUrl {
+                url: base_url.to_string(),
+                reason,
+            })?
+            .to_string();
+
+        let credentials = self.take_credentials()?;
+
+        let http_client = match self.http_client.take() {
+            Some(http_client) => http_client,
+            None => {
+                let timeout = self.timeout.unwrap_or(DEFAULT_TIMEOUT);
+
+                if timeout.is_zero() {
+                    return Err(BuildError::InvalidTimeout);
+                }
+
+                reqwest::Client::builder()
+                    .timeout(timeout)
+                    .build()
+                    .map_err(|err| BuildError::HttpClient(err.to_string()))?
+            }
+        };
+
+        Ok(self.into_client(base_url, credentials, http_client))
+    }
+
+    /// Builds the client without validating the configuration, use
+    /// `try_build` to catch configuration errors early. An invalid API secret
+    /// fails the signed requests instead.
+    ///
+    /// Panics if the http client cannot be built, e.g. when the TLS backend
+    /// fails to initialize.
+    pub fn build(mut self) -> Client {
+        let base_url = self
+            .base_url
+            .as_deref()
+            .unwrap_or(DEFAULT_BASE_URL)
+            .trim_end_matches('/')
+            .to_string();
+
+        let credentials = match self.take_credentials() {
+            Ok(credentials) => credentials,
+            // Signed requests fail with `Error::Unauthorized`.
+            Err(BuildError::IncompleteCredentials) => None,
+            Err(err) => Some(CredentialsSource::Invalid(err.to_string())),
+        };
+
+        let http_client = self.http_client.take().unwrap_or_else(|| {
+            reqwest::Client::builder()
+                .timeout(self.timeout.unwrap_or(DEFAULT_TIMEOUT))
+                .build()
+                .expect("failed to build the http client")
+        });
+
+        self.into_client(base_url, credentials, http_client)
+    }
+
+    fn take_credentials(&mut self) -> std::result::Result<Option<CredentialsSource>, BuildError> {
+        let credentials = match (
+            self.credentials_callback.take(),
+            self.api_key.take(),
+            self.api_secret.take(),
+        ) {
+            (Some(callback), None, None) => Some(CredentialsSource::Callback(callback)),
+            (Some(_), _, _) => return Err(BuildError::ConflictingCredentials),
+            (None, Some(api_key), Some(api_secret)) => Some(CredentialsSource::Static {
+                api_key,
+                signing_key: SigningKey::decode(&api_secret).map_err(BuildError::InvalidSecret)?,
+            }),
+            (None, None, None) => None,
+            _ => return Err(BuildError::IncompleteCredentials),
+        };
+
+        Ok(credentials)
+    }
+
+    fn into_client(
+        self,
+        base_url: String,
+        credentials: Option<CredentialsSource>,
+        http_client: reqwest::Client,
+    ) -> Client {
         Client {
-            base_url: self
-                .base_url
-                .unwrap_or_else(|| DEFAULT_BASE_URL.to_string()),
+            base_url,
             user_agent: self
                 .user_agent
                 .unwrap_or_else(|| DEFAULT_USER_AGENT.to_string()),
-            api_key: self.api_key,
-            api_secret: self.api_secret,
-            http_client: self.http_client.unwrap_or_else(|| {
-                reqwest::Client::builder()
-                    .timeout(self.timeout.unwrap_or(DEFAULT_TIMEOUT))
-                    .build()
-                    .unwrap()
-            }),
+            credentials,
+            otp: self.otp,
+            interceptors: self.interceptors,
+            clock: Arc::new(Clock::new(
+                self.sync_clock,
+                self.max_clock_skew.unwrap_or(DEFAULT_MAX_CLOCK_SKEW),
+                self.on_clock_skew,
+            )),
+            raw_capture: None,
+            http_client,
         }
     }
 }
@@ -95,8 +257,14 @@ pub struct Client {
     /// You must supply a user agent string while creating a request header else you
     /// will not be able to connect to the API.
     user_agent: String,
-    api_key: Option<String>,
-    api_secret: Option<String>,
+    credentials: Option<CredentialsSource>,
+    /// Two-factor password source, for API keys with 2FA enabled.
+    otp: Option<Otp>,
+    interceptors: Interceptors,
+    /// The offset to the server clock, shared by all clones.
+    pub(crate) clock: Arc<Clock>,
+    /// Set while capturing raw responses, see `with_raw_response`.
+    pub(crate) raw_capture: Option<RawCapture>,
     http_client: reqwest::Client,
 }
 
@@ -107,7 +275,7 @@ impl Default for Client {
 }
 
 impl Client {
-    pub fn new(api_key: impl Into<String>, api_secret: impl Into<String>) -> Self {
+    pub fn new(api_key: impl Into<String>, api_secret: impl Into<SecretString>) -> Self {
         Self::builder()
             .api_key(api_key)
             .api_secret(api_secret)
@@ -122,7 +290,34 @@ impl Client {
     where
         Resp: DeserializeOwned,
     {
-        let resp: ResponseWrapper<Resp> = resp.json().await?;
+        let status = resp.status().as_u16();
+        let headers = self
+            .raw_capture
+            .as_ref()
+            .map(|_| resp.headers().clone())
+            .unwrap_or_default();
+        let body = resp.text().await?;
+
+        if let Some(capture) = &self.raw_capture {
+            *capture.lock().unwrap() = Some(RawResponse {
+                status,
+                headers,
+                body: body.clone(),
+            });
+        }
+
+        let resp: ResponseWrapper<Resp> = match decode_str(&body) {
+            Ok(resp) => resp,
+            // Gateways answer with HTML or empty bodies, e.g. during
+            // maintenance, report the status instead of the decode error.
+            Err(_) if !(200..300).contains(&status) => {
+                return Err(Error::FailedRequest {
+                    err: format!("HTTP status {status}"),
+                    status: Some(status),
+                });
+            }
+            Err(err) => return Err(err),
+        };
 
         if !resp.error.is_empty() {
             return Err(Error::Api(resp.error.join(",")));
@@ -141,16 +336,113 @@ impl Client {
     where
         Resp: DeserializeOwned,
     {
+        let request = || RequestInfo::get(url);
         let url = format!("{}{}", self.base_url, url);
 
-        let resp = self
+        self.intercept(request, async {
+            let resp = self
+                .http_client
+                .get(&url)
+                .header(header::USER_AGENT, &self.user_agent)
+                .send()
+                .await
+                .map_err(Error::from);
+
+            self.decode_response(resp).await
+        })
+        .await
+    }
+
+    /// Runs the interceptors around a call. The request description is only
+    /// built if there are interceptors.
+    async fn intercept<T>(
+        &self,
+        request: impl FnOnce() -> RequestInfo,
+        call: impl Future<Output = (Option<u16>, Result<T>)>,
+    ) -> Result<T> {
+        if self.interceptors.is_empty() {
+            return call.await.1;
+        }
+
+        let request = request();
+
+        self.interceptors.before_request(&request)?;
+
+        let started = Instant::now();
+        let (status, result) = call.await;
+
+        self.interceptors.after_response(
+            &request,
+            &ResponseInfo {
+                latency: started.elapsed(),
+                status,
+                error: result.as_ref().err(),
+            },
+        );
+
+        result
+    }
+
+    /// Decodes a response, also returning its HTTP status.
+    async fn decode_response<Resp>(
+        &self,
+        resp: Result<reqwest::Response>,
+    ) -> (Option<u16>, Result<Resp>)
+    where
+        Resp: DeserializeOwned,
+    {
+        match resp {
+            Ok(resp) => (
+                Some(resp.status().as_u16()),
+                self.unwrap_response(resp).await,
+            ),
+            Err(err) => {
+                let status = match &err {
+                    Error::FailedRequest { status, .. } => *status,
+                    _ => None,
+                };
+
+                (status, Err(err))
+            }
+        }
+    }
+
+    async fn post_private(&self, url: &str, query: Option<String>) -> Result<reqwest::Response> {
+        let Some(credentials) = &self.credentials else {
+            return Err(Error::Unauthorized);
+        };
+        let (api_key, signing_key) = credentials.resolve()?;
+
+        let pathname = url;
+        let url = format!("{}{}", self.base_url, url);
+
+        self.ensure_clock_synced().await?;
+
+        let nonce = self.clock.nonce().to_string();
+
+        let mut body = if let Some(query) = query {
+            format!("{}&nonce={}", query, nonce)
+        } else {
+            format!("nonce={}", nonce)
+        };
+
+        if let Some(otp) = &self.otp {
+            body.push_str(&format!("&otp={}", urlencoding::encode(&otp.generate())));
+        }
+
+        Ok(self
             .http_client
-            .get(&url)
+            .post(&url)
+            .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
             .header(header::USER_AGENT, &self.user_agent)
+            .header("API-Key", &api_key)
+            .header(
+                "API-Sign",
+                sign::compute_signature(signing_key.as_bytes(), pathname, &nonce, &body)?,
+            )
+            .body(body)
             .send()
-            .await?;
-
-        self.unwrap_response(resp).await
+            .await?)
     }
 
     // #todo the parameter is path, not url!
@@ -159,39 +451,92 @@ impl Client {
     where
         Resp: DeserializeOwned,
     {
-        let resp = if let Some(api_key) = &self.api_key {
-            if let Some(api_secret) = &self.api_secret {
-                let pathname = url;
-                let url = format!("{}{}", self.base_url, url);
+        let request = || RequestInfo::post_form(url, query.as_deref());
 
-                let nonce = sign::compute_nonce().to_string();
+        self.intercept(request, async {
+            let resp = self.post_private(url, query.clone()).await;
 
-                let body = if let Some(query) = query {
-                    format!("{}&nonce={}", query, nonce)
-                } else {
-                    format!("nonce={}", nonce)
-                };
+            self.decode_response(resp).await
+        })
+        .await
+    }
 
-                self.http_client
-                    .post(&url)
-                    .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
-                    .header(header::USER_AGENT, &self.user_agent)
-                    .header("API-Key", api_key)
-                    .header(
-                        "API-Sign",
-                        sign::compute_signature(api_secret, pathname, &nonce, &body)?,
-                    )
-                    .body(body)
-                    .send()
-                    .await?
-            } else {
-                return Err(Error::Unauthorized);
+    /// Sends a private request to the API that responds with a binary body,
+    /// e.g. a zip archive. Errors are still reported as JSON.
+    pub async fn send_private_bytes(&self, url: &str, query: Option<String>) -> Result<Vec<u8>> {
+        let request = || RequestInfo::post_form(url, query.as_deref());
+
+        self.intercept(request, async {
+            let resp = match self.post_private(url, query.clone()).await {
+                Ok(resp) => resp,
+                Err(err) => return self.decode_response(Err(err)).await,
+            };
+
+            let status = Some(resp.status().as_u16());
+
+            let is_json = resp
+                .headers()
+                .get(header::CONTENT_TYPE)
+                .and_then(|value| value.to_str().ok())
+                .is_some_and(|value| value.starts_with("application/json"));
+
+            if is_json {
+                let result = self
+                    .unwrap_response::<Value>(resp)
+                    .await
+                    .and(Err(Error::internal(
+                        "expected a binary response, received JSON",
+                    )));
+
+                return (status, result);
             }
-        } else {
+
+            let bytes = resp.bytes().await.map(|bytes| bytes.to_vec());
+
+            (status, bytes.map_err(Error::from))
+        })
+        .await
+    }
+
+    async fn post_private_json(&self, url: &str, json: Value) -> Result<reqwest::Response> {
+        let Some(credentials) = &self.credentials else {
             return Err(Error::Unauthorized);
         };
+        let (api_key, signing_key) = credentials.resolve()?;
+
+        let pathname = url;
+        let url = format!("{}{}", self.base_url, url);
+
+        self.ensure_clock_synced().await?;
+
+        let nonce = self.clock.nonce().to_string();
+
+        let mut json = json;
+
+        // #todo handle the unwrap.
+        let data = json.as_object_mut().unwrap();
+
+        data.insert("nonce".into(), nonce.clone().into());
+
+        if let Some(otp) = &self.otp {
+            data.insert("otp".into(), otp.generate().into());
+        }
 
-        self.unwrap_response(resp).await
+        let body = json.to_string();
+
+        Ok(self
+            .http_client
+            .post(&url)
+            .header(header::CONTENT_TYPE, "application/json")
+            .header(header::USER_AGENT, &self.user_agent)
+            .header("API-Key", &api_key)
+            .header(
+                "API-Sign",
+                sign::compute_signature(signing_key.as_bytes(), pathname, &nonce, &body)?,
+            )
+            .body(body)
+            .send()
+            .await?)
     }
 
     // #todo the parameter is path, not url!
@@ -200,41 +545,73 @@ impl Client {
     where
         Resp: DeserializeOwned,
     {
-        let resp = if let Some(api_key) = &self.api_key {
-            if let Some(api_secret) = &self.api_secret {
-                let pathname = url;
-                let url = format!("{}{}", self.base_url, url);
-
-                let nonce = sign::compute_nonce().to_string();
-
-                let mut json = json;
-
-                // #todo handle the unwrap.
-                let data = json.as_object_mut().unwrap();
-
-                data.insert("nonce".into(), nonce.clone().into());
-
-                let body = json.to_string();
-
-                self.http_client
-                    .post(&url)
-                    .header(header::CONTENT_TYPE, "application/json")
-                    .header(header::USER_AGENT, &self.user_agent)
-                    .header("API-Key", api_key)
-                    .header(
-                        "API-Sign",
-                        sign::compute_signature(api_secret, pathname, &nonce, &body)?,
-                    )
-                    .body(body)
-                    .send()
-                    .await?
-            } else {
-                return Err(Error::Unauthorized);
-            }
-        } else {
-            return Err(Error::Unauthorized);
-        };
+        let request = || RequestInfo::post_json(url, &json);
+
+        self.intercept(request, async {
+            let resp = self.post_private_json(url, json.clone()).await;
+
+            self.decode_response(resp).await
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BuildError, Client, Error};
+    use std::time::Duration;
+
+    #[test]
+    fn try_build_validates_configuration() {
+        assert!(Client::builder()
+            .base_url("http://localhost:8080/")
+            .try_build()
+            .is_ok());
+
+        assert!(matches!(
+            Client::builder().base_url("api.kraken.com").try_build(),
+            Err(BuildError::InvalidBaseUrl { .. })
+        ));
+        assert!(matches!(
+            Client::builder()
+                .base_url("ftp://api.kraken.com")
+                .try_build(),
+            Err(BuildError::InvalidBaseUrl { .. })
+        ));
+        assert!(matches!(
+            Client::builder().api_key("my-api-key").try_build(),
+            Err(BuildError::IncompleteCredentials)
+        ));
+        assert!(matches!(
+            Client::builder()
+                .auth("my-api-key", "not base64!")
+                .try_build(),
+            Err(BuildError::InvalidSecret(_))
+        ));
+        assert!(matches!(
+            Client::builder().timeout(Duration::ZERO).try_build(),
+            Err(BuildError::InvalidTimeout)
+        ));
+    }
 
-        self.unwrap_response(resp).await
+    #[tokio::test]
+    async fn build_defers_configuration_errors_to_signed_requests() {
+        let client = Client::builder()
+            .base_url("api.kraken.com")
+            .timeout(Duration::ZERO)
+            .auth("my-api-key", "not base64!")
+            .build();
+
+        assert!(matches!(
+            client.get_account_balance().send().await,
+            Err(Error::Internal(_))
+        ));
+
+        let client = Client::builder().api_key("my-api-key").build();
+
+        assert!(matches!(
+            client.get_account_balance().send().await,
+            Err(Error::Unauthorized)
+        ));
     }
 }
@@ -0,0 +1,236 @@
+//! Alignment of signed requests with the server clock.
+//!
+//! Nonces and order deadlines are computed from the local clock, so a
+//! drifting clock can get requests rejected, e.g. with `EAPI:Invalid nonce` or
+//! `EOrder:Deadline elapsed`. The offset to the server clock is measured with
+//! `/0/public/Time` and applied to both.
+
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use futures::lock::Mutex;
+
+use crate::{sign, Client, Result};
+
+/// The default skew above which the skew callback is called.
+pub const DEFAULT_MAX_CLOCK_SKEW: Duration = Duration::from_secs(1);
+
+/// A measurement of the offset between the server and the local clock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClockSkew {
+    /// Server time minus local time, in milliseconds.
+    pub offset_ms: i64,
+    /// The round trip time of the measurement.
+    pub round_trip: Duration,
+}
+
+impl ClockSkew {
+    /// The server reports time in seconds, so offsets are only accurate to
+    /// about half a second plus half the round trip.
+    pub fn exceeds(&self, max_skew: Duration) -> bool {
+        self.offset_ms.unsigned_abs() > max_skew.as_millis() as u64
+    }
+}
+
+pub(crate) type SkewCallback = Arc<dyn Fn(&ClockSkew) + Send + Sync>;
+
+/// The measured clock offset, shared by all clones of a client.
+pub(crate) struct Clock {
+    offset_ms: AtomicI64,
+    synced: AtomicBool,
+    /// Held while the first measurement is taken, so concurrent requests wait
+    /// for it instead of measuring again.
+    sync_lock: Mutex<()>,
+    last_nonce: AtomicU64,
+    /// Measure the offset before the first signed request.
+    pub(crate) auto_sync: bool,
+    pub(crate) max_skew: Duration,
+    pub(crate) on_skew: Option<SkewCallback>,
+}
+
+impl Clock {
+    pub(crate) fn new(auto_sync: bool, max_skew: Duration, on_skew: Option<SkewCallback>) -> Self {
+        Self {
+            offset_ms: AtomicI64::new(0),
+            synced: AtomicBool::new(false),
+            sync_lock: Mutex::new(()),
+            last_nonce: AtomicU64::new(0),
+            auto_sync,
+            max_skew,
+            on_skew,
+        }
+    }
+
+    pub(crate) fn is_synced(&self) -> bool {
+        self.synced.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn offset_ms(&self) -> Option<i64> {
+        self.is_synced()
+            .then(|| self.offset_ms.load(Ordering::Relaxed))
+    }
+
+    /// Records a measurement, given the server unix time and the local times
+    /// in milliseconds before and after the request.
+    pub(crate) fn record(&self, server_unixtime: i64, sent_ms: u64, received_ms: u64) -> ClockSkew {
+        let local_ms = (sent_ms + received_ms) / 2;
+        // The server truncates to seconds, assume the middle of the second.
+        let server_ms = server_unixtime * 1000 + 500;
+
+        let skew = ClockSkew {
+            offset_ms: server_ms - local_ms as i64,
+            round_trip: Duration::from_millis(received_ms.saturating_sub(sent_ms)),
+        };
+
+        self.offset_ms.store(skew.offset_ms, Ordering::Relaxed);
+        self.synced.store(true, Ordering::Relaxed);
+
+        if let Some(on_skew) = &self.on_skew {
+            if skew.exceeds(self.max_skew) {
+                on_skew(&skew);
+            }
+        }
+
+        skew
+    }
+
+    /// Returns the current time in milliseconds, aligned with the server.
+    pub(crate) fn now_ms(&self) -> u64 {
+        let offset_ms = self.offset_ms.load(Ordering::Relaxed);
+
+        sign::compute_nonce().saturating_add_signed(offset_ms)
+    }
+
+    /// Returns a nonce from the aligned clock. Nonces never decrease, even if
+    /// a new measurement moves the clock back.
+    pub(crate) fn nonce(&self) -> u64 {
+        let now = self.now_ms();
+
+        let previous = self
+            .last_nonce
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |last| {
+                Some(now.max(last + 1))
+            })
+            .unwrap_or_default();
+
+        now.max(previous + 1)
+    }
+}
+
+impl fmt::Debug for Clock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Clock")
+            .field("offset_ms", &self.offset_ms())
+            .field("auto_sync", &self.auto_sync)
+            .field("max_skew", &self.max_skew)
+            .finish()
+    }
+}
+
+impl Client {
+    /// Measures the offset between the server and the local clock, and uses
+    /// it for nonces and deadlines from now on.
+    pub async fn sync_clock(&self) -> Result<ClockSkew> {
+        let started = Instant::now();
+        let sent_ms = sign::compute_nonce();
+
+        let resp = self.get_server_time().send().await?;
+
+        let received_ms = sent_ms + started.elapsed().as_millis() as u64;
+
+        Ok(self.clock.record(resp.unixtime, sent_ms, received_ms))
+    }
+
+    /// The last measured offset of the server clock to the local clock in
+    /// milliseconds, `None` if it was never measured.
+    pub fn clock_offset_ms(&self) -> Option<i64> {
+        self.clock.offset_ms()
+    }
+
+    /// The current time, aligned with the server clock if it was measured.
+    pub fn server_now(&self) -> DateTime<Utc> {
+        DateTime::from_timestamp_millis(self.clock.now_ms() as i64).unwrap_or_else(Utc::now)
+    }
+
+    /// Measures the clock offset if requested and not measured yet.
+    pub(crate) async fn ensure_clock_synced(&self) -> Result<()> {
+        if self.clock.auto_sync && !self.clock.is_synced() {
+            let _guard = self.clock.sync_lock.lock().await;
+
+            // Another request may have measured it while this one waited.
+            if !self.clock.is_synced() {
+                self.sync_clock().await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Clock;
+    use crate::mock::MockKraken;
+    use std::sync::atomic::{AtomicI64, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[test]
+    fn clock_records_skew_and_keeps_nonces_increasing() {
+        let warned = Arc::new(AtomicI64::new(0));
+        let on_skew = {
+            let warned = warned.clone();
+            Arc::new(move |skew: &super::ClockSkew| warned.store(skew.offset_ms, Ordering::SeqCst))
+        };
+
+        let clock = Clock::new(false, Duration::from_secs(1), Some(on_skew));
+
+        assert_eq!(clock.offset_ms(), None);
+
+        // The local clock is 10 seconds behind the server.
+        let skew = clock.record(1_700_000_010, 1_700_000_000_400, 1_700_000_000_600);
+
+        assert_eq!(skew.offset_ms, 10_000);
+        assert_eq!(skew.round_trip, Duration::from_millis(200));
+        assert_eq!(warned.load(Ordering::SeqCst), 10_000);
+
+        let nonce = clock.nonce();
+
+        // The local clock is now 10 seconds ahead, nonces must not go back.
+        clock.record(1_700_000_000, 1_700_000_010_400, 1_700_000_010_600);
+
+        assert_eq!(clock.offset_ms(), Some(-10_000));
+        assert!(clock.nonce() > nonce);
+    }
+
+    #[tokio::test]
+    async fn concurrent_requests_sync_the_clock_once() {
+        let mock = MockKraken::start().await;
+        mock.serve("public/Time").await;
+        mock.serve("private/Balance").await;
+
+        let client = crate::Client::builder()
+            .base_url(&mock.uri())
+            .auth(crate::mock::API_KEY, crate::mock::API_SECRET)
+            .sync_clock(true)
+            .build();
+
+        let balances = (0..4).map(|_| client.get_account_balance().send());
+
+        for balance in futures::future::join_all(balances).await {
+            balance.unwrap();
+        }
+
+        let time_requests = mock
+            .requests()
+            .await
+            .into_iter()
+            .filter(|request| request.url.path() == "/0/public/Time")
+            .count();
+
+        assert_eq!(time_requests, 1);
+    }
+}
@@ -0,0 +1,219 @@
+//! API credentials that do not leak through `Debug` and are wiped from memory
+//! when dropped.
+
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+use base64::{engine::general_purpose::STANDARD as base64_engine, Engine as _};
+use zeroize::Zeroize;
+
+use crate::{error::Error, Result};
+
+pub use kraken_common::SecretString;
+
+/// The environment variable holding the API key, read by `from_env`.
+pub const API_KEY_VAR: &str = "KRAKEN_API_KEY";
+
+/// The environment variable holding the API secret, read by `from_env`.
+pub const API_SECRET_VAR: &str = "KRAKEN_API_SECRET";
+
+/// The decoded API secret, used as the HMAC key when signing requests.
+#[derive(Clone)]
+pub(crate) struct SigningKey(Vec<u8>);
+
+impl SigningKey {
+    /// Decodes the base64 encoded API secret.
+    pub(crate) fn decode(secret: &SecretString) -> std::result::Result<Self, String> {
+        base64_engine
+            .decode(secret.expose())
+            .map(Self)
+            .map_err(|err| err.to_string())
+    }
+
+    pub(crate) fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl fmt::Debug for SigningKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[REDACTED]")
+    }
+}
+
+impl Drop for SigningKey {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+/// An API key and its secret.
+#[derive(Clone)]
+pub struct Credentials {
+    api_key: String,
+    api_secret: SecretString,
+}
+
+impl Credentials {
+    pub fn new(api_key: impl Into<String>, api_secret: impl Into<SecretString>) -> Self {
+        Self {
+            api_key: api_key.into(),
+            api_secret: api_secret.into(),
+        }
+    }
+
+    /// Reads the credentials from the `KRAKEN_API_KEY` and `KRAKEN_API_SECRET`
+    /// environment variables.
+    pub fn from_env() -> Result<Self> {
+        Self::from_env_vars(API_KEY_VAR, API_SECRET_VAR)
+    }
+
+    /// Reads the credentials from the given environment variables.
+    pub fn from_env_vars(api_key_var: &str, api_secret_var: &str) -> Result<Self> {
+        let read =
+            |var: &str| std::env::var(var).map_err(|err| Error::internal(format!("{var}: {err}")));
+
+        Ok(Self::new(read(api_key_var)?, read(api_secret_var)?))
+    }
+
+    /// Reads the credentials from a file with the API key on the first line
+    /// and the API secret on the second line.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let contents = SecretString::new(fs::read_to_string(path)?);
+        let mut lines = contents
+            .expose()
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty());
+
+        match (lines.next(), lines.next()) {
+            (Some(api_key), Some(api_secret)) => Ok(Self::new(api_key, api_secret)),
+            _ => Err(Error::internal(
+                "credentials file must contain the API key and secret on separate lines",
+            )),
+        }
+    }
+
+    pub fn api_key(&self) -> &str {
+        &self.api_key
+    }
+
+    pub fn api_secret(&self) -> &SecretString {
+        &self.api_secret
+    }
+}
+
+impl fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Credentials")
+            .field("api_key", &self.api_key)
+            .field("api_secret", &self.api_secret)
+            .finish()
+    }
+}
+
+/// Returns the credentials to sign a request with, e.g. by querying a secret
+/// manager or the system keychain.
+pub type CredentialsCallback = Arc<dyn Fn() -> Result<Credentials> + Send + Sync>;
+
+/// Where a client gets the credentials for signed requests from.
+#[derive(Clone)]
+pub(crate) enum CredentialsSource {
+    /// Fixed credentials, the secret is decoded once.
+    Static {
+        api_key: String,
+        signing_key: SigningKey,
+    },
+    /// Called for every signed request, so rotated credentials are picked up
+    /// without rebuilding the client.
+    Callback(CredentialsCallback),
+    /// Credentials rejected by `ClientBuilder::build`, the error is reported
+    /// by every signed request.
+    Invalid(String),
+}
+
+impl CredentialsSource {
+    /// Returns the API key and the decoded secret to sign a request with.
+    pub(crate) fn resolve(&self) -> Result<(String, SigningKey)> {
+        match self {
+            Self::Static {
+                api_key,
+                signing_key,
+            } => Ok((api_key.clone(), signing_key.clone())),
+            Self::Callback(callback) => {
+                let credentials = callback()?;
+                let signing_key = SigningKey::decode(credentials.api_secret()).map_err(|err| {
+                    Error::internal(format!("invalid api secret, expected base64: {err}"))
+                })?;
+
+                Ok((credentials.api_key, signing_key))
+            }
+            Self::Invalid(message) => Err(Error::internal(message)),
+        }
+    }
+}
+
+impl fmt::Debug for CredentialsSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Static { api_key, .. } => f
+                .debug_struct("Static")
+                .field("api_key", api_key)
+                .field("signing_key", &"[REDACTED]")
+                .finish(),
+            Self::Callback(_) => write!(f, "Callback"),
+            Self::Invalid(message) => f.debug_tuple("Invalid").field(message).finish(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::Credentials;
+    use crate::mock::{MockKraken, API_KEY, API_SECRET};
+    use crate::Client;
+
+    #[test]
+    fn credentials_are_redacted() {
+        let path = std::env::temp_dir().join(format!("credentials-{}", std::process::id()));
+
+        std::fs::write(&path, "my-api-key\nc2VjcmV0LXNlY3JldA==\n").unwrap();
+
+        let credentials = Credentials::from_file(&path).unwrap();
+
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(credentials.api_key(), "my-api-key");
+        assert!(!format!("{:?}", credentials).contains("c2VjcmV0"));
+
+        let client = Client::builder().credentials(credentials).build();
+
+        assert!(!format!("{:?}", client).contains("c2VjcmV0"));
+        assert!(Client::builder()
+            .auth("my-api-key", "not base64!")
+            .try_build()
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn credentials_callback_picks_up_rotated_keys() {
+        let mock = MockKraken::start().await;
+        mock.serve("private/Balance").await;
+
+        let calls = AtomicUsize::new(0);
+        let client = Client::builder()
+            .base_url(&mock.uri())
+            .credentials_callback(move || match calls.fetch_add(1, Ordering::SeqCst) {
+                0 => Ok(Credentials::new("revoked-api-key", API_SECRET)),
+                _ => Ok(Credentials::new(API_KEY, API_SECRET)),
+            })
+            .build();
+
+        assert!(client.get_account_balance().send().await.is_err());
+        assert!(client.get_account_balance().send().await.is_ok());
+    }
+}
@@ -12,12 +12,60 @@ pub enum Error {
     Unauthorized,
     #[error("api error: {0}")]
     Api(String),
+    #[error("invalid order: {0}")]
+    InvalidOrder(String),
+    #[error("request rejected: {0}")]
+    Rejected(String),
+    #[error("account error: {0}")]
+    Account(String),
+    #[error("cannot decode response at `{path}`: {message}, in `{snippet}`")]
+    Decode {
+        path: String,
+        message: String,
+        snippet: String,
+    },
+}
+
+/// Errors returned by `ClientBuilder::try_build`.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum BuildError {
+    #[error("invalid base url `{url}`: {reason}")]
+    InvalidBaseUrl { url: String, reason: String },
+    #[error("the api key and secret must be set together")]
+    IncompleteCredentials,
+    #[error("set either the api key and secret or a credentials callback, not both")]
+    ConflictingCredentials,
+    #[error("invalid api secret, expected base64: {0}")]
+    InvalidSecret(String),
+    #[error("the timeout must be greater than zero")]
+    InvalidTimeout,
+    #[error("failed to build the http client: {0}")]
+    HttpClient(String),
 }
 
 impl Error {
     pub(crate) fn internal(message: impl fmt::Display) -> Self {
         Self::Internal(message.to_string())
     }
+
+    pub(crate) fn invalid_order(message: impl fmt::Display) -> Self {
+        Self::InvalidOrder(message.to_string())
+    }
+
+    pub(crate) fn account(message: impl fmt::Display) -> Self {
+        Self::Account(message.to_string())
+    }
+
+    /// Returns true if the API rejected the request because the rate limit was
+    /// exceeded, e.g. `EAPI:Rate limit exceeded` or `EGeneral:Too many
+    /// requests`.
+    pub fn is_rate_limit(&self) -> bool {
+        matches!(
+            self,
+            Self::Api(message)
+                if message.contains("Rate limit exceeded") || message.contains("Too many requests")
+        )
+    }
 }
 
 impl From<reqwest::Error> for Error {
@@ -35,8 +83,32 @@ impl From<crypto_common::InvalidLength> for Error {
     }
 }
 
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Internal(e.to_string())
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Self::Internal(e.to_string())
+    }
+}
+
 impl From<std::time::SystemTimeError> for Error {
     fn from(e: std::time::SystemTimeError) -> Self {
         Self::Internal(e.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Error;
+
+    #[test]
+    fn is_rate_limit_matches_both_rate_limit_errors() {
+        assert!(Error::Api("EAPI:Rate limit exceeded".into()).is_rate_limit());
+        assert!(Error::Api("EGeneral:Too many requests".into()).is_rate_limit());
+        assert!(!Error::Api("EOrder:Insufficient funds".into()).is_rate_limit());
+    }
+}
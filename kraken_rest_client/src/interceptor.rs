@@ -0,0 +1,225 @@
+//! Hooks that observe, and optionally veto, every call made through the
+//! client, e.g. for logging, metrics, auditing or a trading kill switch.
+//!
+//! ```no_run
+//! use kraken_rest_client::interceptor::{Interceptor, RequestInfo};
+//! use kraken_rest_client::{Client, Error, Result};
+//!
+//! struct KillSwitch;
+//!
+//! impl Interceptor for KillSwitch {
+//!     fn before_request(&self, request: &RequestInfo) -> Result<()> {
+//!         if request.path == "/0/private/AddOrder" {
+//!             return Err(Error::Rejected("trading is disabled".into()));
+//!         }
+//!         Ok(())
+//!     }
+//! }
+//!
+//! let client = Client::builder().interceptor(KillSwitch).build();
+//! ```
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::Method;
+use serde_json::{Map, Value};
+
+use crate::{Error, Result};
+
+/// Parameters that are never passed to interceptors.
+const REDACTED_PARAMS: &[&str] = &["nonce", "otp"];
+
+/// A call about to be sent to the API.
+#[derive(Debug, Clone)]
+pub struct RequestInfo {
+    pub method: Method,
+    /// The request path without the query string, e.g. `/0/private/AddOrder`.
+    pub path: String,
+    /// The request parameters as a JSON object, without the nonce and the
+    /// two-factor password. Form and query parameters are strings.
+    pub params: Value,
+}
+
+impl RequestInfo {
+    /// Describes a GET request, splitting the query string off the path.
+    pub(crate) fn get(path_and_query: &str) -> Self {
+        let (path, query) = path_and_query
+            .split_once('?')
+            .unwrap_or((path_and_query, ""));
+
+        Self {
+            method: Method::GET,
+            path: path.to_string(),
+            params: form_params(query),
+        }
+    }
+
+    /// Describes a form encoded POST request.
+    pub(crate) fn post_form(path: &str, query: Option<&str>) -> Self {
+        Self {
+            method: Method::POST,
+            path: path.to_string(),
+            params: form_params(query.unwrap_or_default()),
+        }
+    }
+
+    /// Describes a JSON POST request.
+    pub(crate) fn post_json(path: &str, json: &Value) -> Self {
+        let mut params = json.as_object().cloned().unwrap_or_default();
+
+        for name in REDACTED_PARAMS {
+            params.remove(*name);
+        }
+
+        Self {
+            method: Method::POST,
+            path: path.to_string(),
+            params: Value::Object(params),
+        }
+    }
+}
+
+fn form_params(query: &str) -> Value {
+    let mut params = Map::new();
+
+    for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+        let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+        let name = urlencoding::decode(name).unwrap_or_else(|_| name.into());
+
+        if REDACTED_PARAMS.contains(&name.as_ref()) {
+            continue;
+        }
+
+        let value = urlencoding::decode(value).unwrap_or_else(|_| value.into());
+
+        params.insert(name.into_owned(), value.into_owned().into());
+    }
+
+    Value::Object(params)
+}
+
+/// The outcome of a call.
+#[derive(Debug, Clone)]
+pub struct ResponseInfo<'a> {
+    /// The time from sending the request to decoding the response.
+    pub latency: Duration,
+    /// The HTTP status, missing if no response was received.
+    pub status: Option<u16>,
+    /// The error returned to the caller, if any.
+    pub error: Option<&'a Error>,
+}
+
+impl ResponseInfo<'_> {
+    /// Returns the error messages reported by the API, e.g.
+    /// `EOrder:Insufficient funds`.
+    pub fn api_errors(&self) -> Vec<&str> {
+        match self.error {
+            Some(Error::Api(errors)) => errors.split(',').collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Observes every call made through a `Client`.
+///
+/// Interceptors run in the order they were added to the `ClientBuilder`.
+pub trait Interceptor: Send + Sync {
+    /// Called before the request is signed and sent. Returning an error, e.g.
+    /// `Error::Rejected`, vetoes the request and the error is returned to the
+    /// caller.
+    fn before_request(&self, _request: &RequestInfo) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called once the response is decoded, or the request failed.
+    fn after_response(&self, _request: &RequestInfo, _response: &ResponseInfo) {}
+}
+
+/// The interceptors registered on a client.
+#[derive(Clone, Default)]
+pub(crate) struct Interceptors(Vec<Arc<dyn Interceptor>>);
+
+impl Interceptors {
+    pub(crate) fn push(&mut self, interceptor: Arc<dyn Interceptor>) {
+        self.0.push(interceptor);
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub(crate) fn before_request(&self, request: &RequestInfo) -> Result<()> {
+        self.0
+            .iter()
+            .try_for_each(|interceptor| interceptor.before_request(request))
+    }
+
+    pub(crate) fn after_response(&self, request: &RequestInfo, response: &ResponseInfo) {
+        for interceptor in &self.0 {
+            interceptor.after_response(request, response);
+        }
+    }
+}
+
+impl fmt::Debug for Interceptors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Interceptors({})", self.0.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Interceptor, RequestInfo, ResponseInfo};
+    use crate::{Client, Error, OrderSide, OrderType, Result};
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Default)]
+    struct Recorder {
+        calls: Mutex<Vec<(String, Option<u16>, bool)>>,
+    }
+
+    impl Interceptor for Arc<Recorder> {
+        fn before_request(&self, request: &RequestInfo) -> Result<()> {
+            if request.path == "/0/private/AddOrder" {
+                assert_eq!(request.params["pair"], "XBTUSD");
+                return Err(Error::Rejected("kill switch".into()));
+            }
+            Ok(())
+        }
+
+        fn after_response(&self, request: &RequestInfo, response: &ResponseInfo) {
+            self.calls.lock().unwrap().push((
+                request.path.clone(),
+                response.status,
+                response.error.is_some(),
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn interceptors_observe_and_veto_calls() {
+        let recorder = Arc::new(Recorder::default());
+
+        // Nothing listens on port 1, so the public call fails fast.
+        let client = Client::builder()
+            .base_url("http://127.0.0.1:1")
+            .auth("my-api-key", "c2VjcmV0LXNlY3JldA==")
+            .interceptor(recorder.clone())
+            .build();
+
+        let result = client
+            .add_order("XBTUSD", OrderSide::Buy, OrderType::Market, "0.01")
+            .send()
+            .await;
+
+        assert_eq!(result.unwrap_err(), Error::Rejected("kill switch".into()));
+
+        assert!(client.get_server_time().send().await.is_err());
+
+        let calls = recorder.calls.lock().unwrap();
+
+        assert_eq!(calls.as_slice(), [("/0/public/Time".into(), None, true)]);
+    }
+}
@@ -1,12 +1,29 @@
+pub mod accounts;
 pub mod api;
+pub mod backfill;
+#[cfg(feature = "blocking")]
+pub mod blocking;
 pub mod client;
+pub mod clock;
+pub mod credentials;
 pub mod error;
+pub mod interceptor;
+#[cfg(test)]
+mod mock;
+pub mod otp;
+mod paginate;
+pub mod raw;
 mod sign;
+pub mod symbols;
 pub mod types;
+mod util;
+pub mod validator;
 
 pub use api::get_ohlc_data::Interval;
 pub use client::{Client, Result};
-pub use error::Error;
+pub use credentials::Credentials;
+pub use error::{BuildError, Error};
+pub use paginate::DEFAULT_PAGE_DELAY;
 pub use types::*;
 
 pub const MAX_OPEN_ORDERS_COUNT_STARTER: usize = 60;
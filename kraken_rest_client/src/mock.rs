@@ -0,0 +1,164 @@
+//! A local stand-in for the Kraken REST API, for tests.
+//!
+//! Endpoints serve the responses recorded under `fixtures/`, e.g.
+//! `fixtures/private/Balance.json` for `/0/private/Balance`. Private
+//! endpoints only respond to requests signed with `API_SECRET`, like the
+//! exchange they reply `EAPI:Invalid signature` otherwise.
+
+use std::path::Path;
+
+use base64::{engine::general_purpose::STANDARD as base64_engine, Engine as _};
+use serde_json::{json, Value};
+use wiremock::matchers::{method, path};
+use wiremock::{Match, Mock, MockServer, Request, ResponseTemplate};
+
+use crate::{sign, Client};
+
+pub(crate) const API_KEY: &str = "mock-api-key";
+
+/// The base64 encoded secret `kraken-mock-secret`.
+pub(crate) const API_SECRET: &str = "a3Jha2VuLW1vY2stc2VjcmV0";
+
+/// Returns a recorded response, e.g. `fixture("public/Time")`.
+pub(crate) fn fixture(endpoint: &str) -> String {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("fixtures")
+        .join(format!("{endpoint}.json"));
+
+    std::fs::read_to_string(&path)
+        .unwrap_or_else(|err| panic!("cannot read fixture {}: {err}", path.display()))
+}
+
+pub(crate) struct MockKraken {
+    server: MockServer,
+}
+
+impl MockKraken {
+    pub(crate) async fn start() -> Self {
+        Self {
+            server: MockServer::start().await,
+        }
+    }
+
+    pub(crate) fn uri(&self) -> String {
+        self.server.uri()
+    }
+
+    /// A client for the mock server, authenticated with `API_KEY` and
+    /// `API_SECRET`.
+    pub(crate) fn client(&self) -> Client {
+        Client::builder()
+            .base_url(&self.uri())
+            .auth(API_KEY, API_SECRET)
+            .build()
+    }
+
+    /// Serves the recorded response of an endpoint, e.g. `private/Balance`.
+    pub(crate) async fn serve(&self, endpoint: &str) {
+        self.serve_body(endpoint, fixture(endpoint), "application/json")
+            .await;
+    }
+
+    /// Serves a response body, e.g. a zip archive for `private/RetrieveExport`.
+    pub(crate) async fn serve_body(
+        &self,
+        endpoint: &str,
+        body: impl Into<Vec<u8>>,
+        content_type: &str,
+    ) {
+        self.respond(
+            endpoint,
+            ResponseTemplate::new(200).set_body_raw(body, content_type),
+        )
+        .await;
+    }
+
+    /// Responds to an endpoint with an API error, e.g. `EOrder:Insufficient
+    /// funds`.
+    pub(crate) async fn api_error(&self, endpoint: &str, error: &str) {
+        self.respond(endpoint, error_response(error)).await;
+    }
+
+    /// Responds to an endpoint as if the rate limit was exceeded.
+    pub(crate) async fn rate_limit(&self, endpoint: &str) {
+        self.api_error(endpoint, "EAPI:Rate limit exceeded").await;
+    }
+
+    /// Responds to an endpoint with an HTTP error and an empty body, e.g. a
+    /// 503 during maintenance.
+    pub(crate) async fn http_error(&self, endpoint: &str, status: u16) {
+        self.respond(endpoint, ResponseTemplate::new(status)).await;
+    }
+
+    /// Returns the requests received so far.
+    pub(crate) async fn requests(&self) -> Vec<Request> {
+        self.server.received_requests().await.unwrap_or_default()
+    }
+
+    async fn respond(&self, endpoint: &str, response: ResponseTemplate) {
+        let endpoint_path = format!("/0/{endpoint}");
+
+        if endpoint.starts_with("private/") {
+            Mock::given(method("POST"))
+                .and(path(&endpoint_path))
+                .and(SignedRequest)
+                .respond_with(response)
+                .mount(&self.server)
+                .await;
+
+            Mock::given(method("POST"))
+                .and(path(&endpoint_path))
+                .respond_with(error_response("EAPI:Invalid signature"))
+                .with_priority(u8::MAX)
+                .mount(&self.server)
+                .await;
+        } else {
+            Mock::given(method("GET"))
+                .and(path(&endpoint_path))
+                .respond_with(response)
+                .mount(&self.server)
+                .await;
+        }
+    }
+}
+
+fn error_response(error: &str) -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_json(json!({ "error": [error] }))
+}
+
+/// Matches requests signed with `API_KEY` and `API_SECRET`.
+struct SignedRequest;
+
+impl Match for SignedRequest {
+    fn matches(&self, request: &Request) -> bool {
+        let header = |name: &str| request.headers.get(name).and_then(|v| v.to_str().ok());
+
+        let (Some(api_key), Some(api_sign)) = (header("API-Key"), header("API-Sign")) else {
+            return false;
+        };
+
+        let Ok(body) = std::str::from_utf8(&request.body) else {
+            return false;
+        };
+
+        let nonce = if header("Content-Type") == Some("application/json") {
+            serde_json::from_str::<Value>(body)
+                .ok()
+                .and_then(|json| json["nonce"].as_str().map(str::to_string))
+        } else {
+            body.split('&')
+                .find_map(|pair| pair.strip_prefix("nonce="))
+                .map(str::to_string)
+        };
+
+        let Some(nonce) = nonce else {
+            return false;
+        };
+
+        let secret = base64_engine.decode(API_SECRET).unwrap();
+
+        let signature = sign::compute_signature(&secret, request.url.path(), &nonce, body);
+
+        api_key == API_KEY && signature.is_ok_and(|signature| signature == api_sign)
+    }
+}
@@ -0,0 +1,186 @@
+use std::collections::{HashSet, VecDeque};
+use std::future::Future;
+use std::time::Duration;
+
+use futures::{stream, Stream};
+
+use crate::Result;
+
+/// The default pause between consecutive page requests. The private history
+/// endpoints cost 2 points each, so this keeps a long export well within the
+/// counter decay of the starter tier.
+pub const DEFAULT_PAGE_DELAY: Duration = Duration::from_secs(3);
+
+/// The maximum number of times a page is retried after a rate-limit error.
+const MAX_RATE_LIMIT_RETRIES: u32 = 5;
+
+/// A single page of an offset-paginated endpoint.
+pub(crate) trait Page {
+    type Item;
+
+    /// The total number of entries matching the query.
+    fn count(&self) -> usize;
+
+    /// The entries of the page, keyed by id, ordered newest first.
+    fn into_entries(self) -> Vec<(String, Self::Item)>;
+}
+
+struct State<T, F> {
+    fetch: F,
+    page_delay: Duration,
+    ofs: usize,
+    count: Option<usize>,
+    seen: HashSet<String>,
+    buffer: VecDeque<(String, T)>,
+    done: bool,
+}
+
+/// Turns an offset-paginated endpoint into a stream of `(id, entry)` pairs.
+///
+/// Entries that shift into the next page when new records arrive during the
+/// iteration are yielded only once.
+pub(crate) fn paginate<P, F, Fut>(
+    fetch: F,
+    page_delay: Duration,
+) -> impl Stream<Item = Result<(String, P::Item)>>
+where
+    P: Page,
+    F: FnMut(usize) -> Fut,
+    Fut: Future<Output = Result<P>>,
+{
+    let state = State {
+        fetch,
+        page_delay,
+        ofs: 0,
+        count: None,
+        seen: HashSet::new(),
+        buffer: VecDeque::new(),
+        done: false,
+    };
+
+    stream::unfold(state, |mut state| async move {
+        loop {
+            if let Some(entry) = state.buffer.pop_front() {
+                return Some((Ok(entry), state));
+            }
+
+            if state.done || state.count.is_some_and(|count| state.ofs >= count) {
+                return None;
+            }
+
+            if state.count.is_some() && !state.page_delay.is_zero() {
+                tokio::time::sleep(state.page_delay).await;
+            }
+
+            let ofs = state.ofs;
+            let fetch = &mut state.fetch;
+
+            let page = match retry_rate_limited(|| fetch(ofs), state.page_delay).await {
+                Ok(page) => page,
+                Err(error) => {
+                    state.done = true;
+                    return Some((Err(error), state));
+                }
+            };
+
+            state.count = Some(page.count());
+
+            let entries = page.into_entries();
+
+            if entries.is_empty() {
+                state.done = true;
+            }
+
+            // Advance by the raw page size: new records push older ones to
+            // higher offsets, so this may revisit entries but never skips any.
+            state.ofs += entries.len();
+
+            for (id, entry) in entries {
+                if state.seen.insert(id.clone()) {
+                    state.buffer.push_back((id, entry));
+                }
+            }
+        }
+    })
+}
+
+/// Calls `fetch` until it succeeds or fails with an error other than a
+/// rate-limit error, backing off exponentially between attempts.
+pub(crate) async fn retry_rate_limited<T, F, Fut>(mut fetch: F, backoff: Duration) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut backoff = backoff.max(Duration::from_secs(1));
+    let mut retries = 0;
+
+    loop {
+        match fetch().await {
+            Err(error) if error.is_rate_limit() && retries < MAX_RATE_LIMIT_RETRIES => {
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+                retries += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{paginate, Page};
+    use crate::{Error, Result};
+    use futures::StreamExt;
+    use std::time::Duration;
+
+    struct TestPage {
+        count: usize,
+        ids: Vec<&'static str>,
+    }
+
+    impl Page for TestPage {
+        type Item = ();
+
+        fn count(&self) -> usize {
+            self.count
+        }
+
+        fn into_entries(self) -> Vec<(String, ())> {
+            self.ids
+                .into_iter()
+                .map(|id| (id.to_string(), ()))
+                .collect()
+        }
+    }
+
+    #[tokio::test]
+    async fn paginate_skips_entries_shifted_between_pages() {
+        // A new entry `E` arrives after the first page, shifting `C` into the
+        // second page.
+        let pages = [vec!["D", "C"], vec!["C", "B"], vec!["A"]];
+        let mut attempts = 0;
+
+        let ids: Vec<Result<String>> = paginate(
+            |ofs| {
+                attempts += 1;
+                let page = if attempts == 2 {
+                    Err(Error::Api("EAPI:Rate limit exceeded".into()))
+                } else {
+                    Ok(TestPage {
+                        count: if ofs == 0 { 4 } else { 5 },
+                        ids: pages[ofs / 2].clone(),
+                    })
+                };
+                async move { page }
+            },
+            Duration::ZERO,
+        )
+        .map(|entry| entry.map(|(id, _)| id))
+        .collect()
+        .await;
+
+        let ids: Vec<String> = ids.into_iter().map(|id| id.unwrap()).collect();
+
+        assert_eq!(ids, vec!["D", "C", "B", "A"]);
+    }
+}
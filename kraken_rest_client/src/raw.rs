@@ -0,0 +1,216 @@
+//! Access to the raw responses behind typed results, and decoding with
+//! descriptive errors.
+
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+use reqwest::header::HeaderMap;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use serde_path_to_error::{Path, Segment};
+
+use crate::{error::Error, Client, Result};
+
+/// The maximum length of the JSON snippet included in decode errors.
+const SNIPPET_LEN: usize = 120;
+
+/// A response as received from the API.
+#[derive(Debug, Clone)]
+pub struct RawResponse {
+    pub status: u16,
+    pub headers: HeaderMap,
+    /// The response body, usually JSON.
+    pub body: String,
+}
+
+impl RawResponse {
+    /// Parses the body as JSON.
+    pub fn json(&self) -> Result<Value> {
+        decode_str(&self.body)
+    }
+}
+
+/// A typed result together with the raw response it was decoded from.
+#[derive(Debug)]
+pub struct WithRaw<T> {
+    pub result: Result<T>,
+    /// The last response received, `None` if the request was not sent or
+    /// failed before a response arrived.
+    pub raw: Option<RawResponse>,
+}
+
+/// Where the client stores raw responses while capturing.
+pub(crate) type RawCapture = Arc<Mutex<Option<RawResponse>>>;
+
+impl Client {
+    /// Runs a request, returning its typed result together with the raw
+    /// response, e.g. to inspect the payload when decoding fails.
+    ///
+    /// ```no_run
+    /// # async fn run(client: kraken_rest_client::Client) {
+    /// let ohlc = client
+    ///     .with_raw_response(|client| client.get_ohlc_data("XBTUSD").send())
+    ///     .await;
+    ///
+    /// if let (Err(error), Some(raw)) = (&ohlc.result, &ohlc.raw) {
+    ///     eprintln!("{error}, status {}: {}", raw.status, raw.body);
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// If the request makes several calls, e.g. when paginating, `raw` holds
+    /// the last response.
+    pub async fn with_raw_response<T, F, Fut>(&self, request: F) -> WithRaw<T>
+    where
+        F: FnOnce(Client) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let capture = RawCapture::default();
+
+        let mut client = self.clone();
+        client.raw_capture = Some(capture.clone());
+
+        let result = request(client).await;
+        let raw = capture.lock().unwrap().take();
+
+        WithRaw { result, raw }
+    }
+}
+
+/// Decodes a JSON string, reporting the path and a snippet of the offending
+/// JSON on failure.
+pub(crate) fn decode_str<T: DeserializeOwned>(json: &str) -> Result<T> {
+    let de = &mut serde_json::Deserializer::from_str(json);
+
+    serde_path_to_error::deserialize(de).map_err(|err| {
+        let pointer = json_pointer(err.path());
+        let path = err.path().to_string();
+        let inner = err.into_inner();
+
+        let snippet = if inner.is_data() {
+            // The JSON is well-formed, point at the value that failed.
+            serde_json::from_str::<Value>(json)
+                .ok()
+                .and_then(|value| value.pointer(&pointer).map(Value::to_string))
+                .unwrap_or_else(|| snippet_at(json, inner.line(), inner.column()))
+        } else {
+            snippet_at(json, inner.line(), inner.column())
+        };
+
+        Error::Decode {
+            path,
+            message: inner.to_string(),
+            snippet: truncate(&snippet),
+        }
+    })
+}
+
+/// Decodes a JSON value, reporting the path and the offending value on
+/// failure.
+pub(crate) fn decode_value<T: DeserializeOwned>(value: &Value) -> Result<T> {
+    serde_path_to_error::deserialize(value).map_err(|err| {
+        let path = err.path().to_string();
+
+        let snippet = value
+            .pointer(&json_pointer(err.path()))
+            .unwrap_or(value)
+            .to_string();
+
+        Error::Decode {
+            path,
+            message: err.into_inner().to_string(),
+            snippet: truncate(&snippet),
+        }
+    })
+}
+
+/// Converts a `serde_path_to_error` path to a JSON pointer, e.g.
+/// `result.XXBTZUSD[0][1]` to `/result/XXBTZUSD/0/1`. The segments are used
+/// rather than the rendered path, as keys may contain dots, e.g. `ETH2.S`.
+fn json_pointer(path: &Path) -> String {
+    let mut pointer = String::new();
+
+    for segment in path {
+        match segment {
+            Segment::Seq { index } => {
+                pointer.push('/');
+                pointer.push_str(&index.to_string());
+            }
+            Segment::Map { key } | Segment::Enum { variant: key } => {
+                pointer.push('/');
+                pointer.push_str(&key.replace('~', "~0").replace('/', "~1"));
+            }
+            Segment::Unknown => break,
+        }
+    }
+
+    pointer
+}
+
+/// Returns the text around a line and column of `json`.
+fn snippet_at(json: &str, line: usize, column: usize) -> String {
+    let line = json.lines().nth(line.saturating_sub(1)).unwrap_or_default();
+    let column = column.min(line.len());
+
+    let start = (0..=column.saturating_sub(SNIPPET_LEN / 2))
+        .rev()
+        .find(|i| line.is_char_boundary(*i))
+        .unwrap_or(0);
+
+    line[start..].to_string()
+}
+
+fn truncate(snippet: &str) -> String {
+    match snippet.char_indices().nth(SNIPPET_LEN) {
+        Some((end, _)) => format!("{}...", &snippet[..end]),
+        None => snippet.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_str, decode_value};
+    use crate::{api::GetOhlcDataResponse, Error};
+    use std::collections::HashMap;
+
+    #[test]
+    fn decode_errors_include_path_and_snippet() {
+        let json = r#"[[1688671200, "30306.1", "30306.2", "30305.7", "30305.7", "30306.1", "3.39243896", "23"]]"#;
+
+        let err = decode_str::<GetOhlcDataResponse>(json).unwrap_err();
+
+        match err {
+            Error::Decode { path, snippet, .. } => {
+                assert_eq!(path, "[0][7]");
+                assert_eq!(snippet, r#""23""#);
+            }
+            err => panic!("unexpected error {err:?}"),
+        }
+
+        assert!(matches!(
+            decode_str::<GetOhlcDataResponse>(r#"[[1688671200, "#),
+            Err(Error::Decode { .. })
+        ));
+    }
+
+    #[test]
+    fn decode_errors_point_at_keys_with_dots() {
+        let json = r#"{"result": {"XXBT": 1, "ETH2.S": "1.5"}}"#;
+
+        for err in [
+            decode_str::<HashMap<String, HashMap<String, u32>>>(json).unwrap_err(),
+            decode_value::<HashMap<String, HashMap<String, u32>>>(
+                &serde_json::from_str(json).unwrap(),
+            )
+            .unwrap_err(),
+        ] {
+            match err {
+                Error::Decode { path, snippet, .. } => {
+                    assert_eq!(path, "result.ETH2.S");
+                    assert_eq!(snippet, r#""1.5""#);
+                }
+                err => panic!("unexpected error {err:?}"),
+            }
+        }
+    }
+}
@@ -6,7 +6,6 @@ use std::time::{SystemTime, UNIX_EPOCH};
 // #todo Consider extracting as a standalone crate, probably not worth it.
 
 use crate::client::Result;
-use crate::error::Error;
 
 fn sha256(input: String) -> Vec<u8> {
     let mut hasher = Sha256::new();
@@ -26,9 +25,10 @@ pub(crate) fn compute_nonce() -> u64 {
     since_the_epoch.as_millis() as u64
 }
 
-/// Computes the signature of the POST body
+/// Computes the signature of the POST body, `api_secret` is the base64
+/// decoded API secret.
 pub fn compute_signature(
-    api_secret: &str,
+    api_secret: &[u8],
     path: &str,
     nonce: &str,
     post_data: &str,
@@ -45,8 +45,7 @@ pub fn compute_signature(
     to_hash.append(&mut path.as_bytes().to_owned());
     to_hash.append(&mut sha256_res);
 
-    let secret = base64_engine.decode(api_secret).map_err(Error::internal)?;
-    let sha512_res = sha512(to_hash, &secret)?;
+    let sha512_res = sha512(to_hash, api_secret)?;
 
     Ok(base64_engine.encode(sha512_res))
 }
@@ -0,0 +1,362 @@
+//! Asset and pair name resolution from exchange metadata.
+//!
+//! Kraken uses several names for the same asset or pair: legacy X/Z codes
+//! (`XXBT`, `XXBTZUSD`), altnames (`XBT`, `XBTUSD`), websocket v1 names
+//! (`XBT/USD`), websocket v2 names (`BTC/USD`) and futures symbols
+//! (`PF_XBTUSD`). `SymbolRegistry` is built from the `Assets` and
+//! `AssetPairs` endpoints, so it also knows about newly listed assets, and can
+//! be cached on disk between runs.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api::{Asset, GetAssetPairsResponse, GetAssetsResponse, PairInfo},
+    Client, FuturesContract, Instrument, Result, SpotPair,
+};
+
+pub use kraken_common::instrument::{altname_from_ws_v2, ws_v2_asset_name};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Metadata {
+    /// Unix timestamp of when the metadata was fetched
+    fetched_at: u64,
+    assets: GetAssetsResponse,
+    pairs: GetAssetPairsResponse,
+}
+
+/// Maps between the different names of assets and pairs.
+#[derive(Debug, Clone)]
+pub struct SymbolRegistry {
+    metadata: Metadata,
+    /// Maps every known asset name to the asset code.
+    asset_index: HashMap<String, String>,
+    /// Maps every known pair name to the pair key.
+    pair_index: HashMap<String, String>,
+    /// Maps `(base code, quote code)` to the pair key.
+    pair_by_assets: HashMap<(String, String), String>,
+}
+
+impl SymbolRegistry {
+    /// Creates a registry from already fetched metadata.
+    pub fn from_metadata(assets: GetAssetsResponse, pairs: GetAssetPairsResponse) -> Self {
+        Self::from_parts(Metadata {
+            fetched_at: unix_now(),
+            assets,
+            pairs,
+        })
+    }
+
+    fn from_parts(metadata: Metadata) -> Self {
+        let mut asset_index = HashMap::new();
+
+        for (code, asset) in &metadata.assets {
+            asset_index.insert(code.clone(), code.clone());
+            asset_index.insert(asset.altname.clone(), code.clone());
+            asset_index.insert(ws_v2_asset_name(&asset.altname).to_string(), code.clone());
+        }
+
+        let mut registry = Self {
+            metadata,
+            asset_index,
+            pair_index: HashMap::new(),
+            pair_by_assets: HashMap::new(),
+        };
+
+        let mut pair_index = HashMap::new();
+        let mut pair_by_assets = HashMap::new();
+
+        for (key, pair) in &registry.metadata.pairs {
+            pair_index.insert(key.clone(), key.clone());
+            pair_index.insert(pair.altname.clone(), key.clone());
+
+            if let Some(wsname) = &pair.wsname {
+                pair_index.insert(wsname.clone(), key.clone());
+            }
+
+            if let Some(ws_name) = registry.pair_ws_name(pair) {
+                pair_index.insert(ws_name, key.clone());
+            }
+
+            if let Some(futures_symbol) = registry.pair_futures_symbol(pair) {
+                pair_index.insert(futures_symbol, key.clone());
+            }
+
+            pair_by_assets.insert((pair.base.clone(), pair.quote.clone()), key.clone());
+        }
+
+        registry.pair_index = pair_index;
+        registry.pair_by_assets = pair_by_assets;
+        registry
+    }
+
+    /// Fetches the asset and pair metadata.
+    pub async fn load(client: &Client) -> Result<Self> {
+        let assets = client.get_assets().send().await?;
+        let pairs = client.get_asset_pairs().send().await?;
+
+        Ok(Self::from_metadata(assets, pairs))
+    }
+
+    /// Reads the registry from the cache file at `path` if it is younger than
+    /// `max_age`, otherwise fetches the metadata and updates the cache file.
+    pub async fn load_cached(
+        client: &Client,
+        path: impl AsRef<Path>,
+        max_age: Duration,
+    ) -> Result<Self> {
+        let path = path.as_ref();
+
+        if let Ok(registry) = Self::read(path) {
+            if !registry.is_older_than(max_age) {
+                return Ok(registry);
+            }
+        }
+
+        let registry = Self::load(client).await?;
+
+        registry.save(path)?;
+
+        Ok(registry)
+    }
+
+    /// Replaces the metadata with freshly fetched metadata.
+    pub async fn refresh(&mut self, client: &Client) -> Result<()> {
+        *self = Self::load(client).await?;
+        Ok(())
+    }
+
+    /// Reads a registry saved with `save`.
+    pub fn read(path: impl AsRef<Path>) -> Result<Self> {
+        let metadata = serde_json::from_slice(&fs::read(path)?)?;
+
+        Ok(Self::from_parts(metadata))
+    }
+
+    /// Saves the registry to `path`, to be read with `read`.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        fs::write(path, serde_json::to_vec(&self.metadata)?)?;
+        Ok(())
+    }
+
+    /// Unix timestamp of when the metadata was fetched.
+    pub fn fetched_at(&self) -> u64 {
+        self.metadata.fetched_at
+    }
+
+    pub fn is_older_than(&self, max_age: Duration) -> bool {
+        unix_now().saturating_sub(self.metadata.fetched_at) > max_age.as_secs()
+    }
+
+    /// Returns the asset code (e.g. `XXBT`) of an asset code, altname or
+    /// websocket v2 name.
+    pub fn asset_code(&self, name: &str) -> Option<&str> {
+        self.asset_index.get(name).map(String::as_str)
+    }
+
+    /// Returns the asset with the given code, altname or websocket v2 name.
+    pub fn asset(&self, name: &str) -> Option<&Asset> {
+        self.metadata.assets.get(self.asset_code(name)?)
+    }
+
+    /// Returns the altname (e.g. `XBT`) of an asset.
+    pub fn asset_altname(&self, name: &str) -> Option<&str> {
+        self.asset(name).map(|asset| asset.altname.as_str())
+    }
+
+    /// Returns the websocket v2 name (e.g. `BTC`) of an asset.
+    pub fn asset_ws_name(&self, name: &str) -> Option<&str> {
+        self.asset_altname(name).map(ws_v2_asset_name)
+    }
+
+    /// Returns the pair key (e.g. `XXBTZUSD`) of a pair key, altname,
+    /// websocket v1 or v2 name or futures perpetual symbol.
+    pub fn pair_key(&self, name: &str) -> Option<&str> {
+        self.pair_index.get(name).map(String::as_str)
+    }
+
+    /// Returns the pair with the given name, see `pair_key`.
+    pub fn pair(&self, name: &str) -> Option<&PairInfo> {
+        self.metadata.pairs.get(self.pair_key(name)?)
+    }
+
+    /// Returns the key of the pair trading `base` against `quote`, given as
+    /// codes, altnames or websocket v2 names. A replacement for
+    /// `PairName::from` that also works for assets listed after this crate
+    /// was released.
+    pub fn pair_name(&self, base: &str, quote: &str) -> Option<&str> {
+        let base = self.asset_code(base)?.to_string();
+        let quote = self.asset_code(quote)?.to_string();
+
+        self.pair_by_assets.get(&(base, quote)).map(String::as_str)
+    }
+
+    /// Returns the websocket v2 name (e.g. `BTC/USD`) of a pair.
+    pub fn pair_ws_name_of(&self, name: &str) -> Option<String> {
+        self.pair_ws_name(self.pair(name)?)
+    }
+
+    /// Returns the futures perpetual symbol (e.g. `PF_XBTUSD`) for a pair.
+    /// The symbol is derived from the spot altnames, it is not checked that
+    /// the contract is listed.
+    pub fn pair_futures_symbol_of(&self, name: &str) -> Option<String> {
+        self.pair_futures_symbol(self.pair(name)?)
+    }
+
+    /// Returns the spot pair with the given name, see `pair_key`.
+    pub fn spot_pair(&self, name: &str) -> Option<SpotPair> {
+        let pair = self.pair(name)?;
+
+        Some(SpotPair::new(
+            self.asset_altname(&pair.base)?,
+            self.asset_altname(&pair.quote)?,
+        ))
+    }
+
+    /// Parses a futures symbol, e.g. `PF_XBTUSD` or `FI_XBTUSD_230929`,
+    /// resolving the underlying pair.
+    pub fn futures_contract(&self, symbol: &str) -> Option<FuturesContract> {
+        FuturesContract::from_symbol(symbol, |name| self.spot_pair(name))
+    }
+
+    /// Resolves a spot REST, spot websocket or futures name to an instrument.
+    pub fn instrument(&self, name: &str) -> Option<Instrument> {
+        Instrument::parse(name, |name| self.spot_pair(name))
+    }
+
+    fn pair_ws_name(&self, pair: &PairInfo) -> Option<String> {
+        let base = self.metadata.assets.get(&pair.base)?;
+        let quote = self.metadata.assets.get(&pair.quote)?;
+
+        Some(format!(
+            "{}/{}",
+            ws_v2_asset_name(&base.altname),
+            ws_v2_asset_name(&quote.altname)
+        ))
+    }
+
+    fn pair_futures_symbol(&self, pair: &PairInfo) -> Option<String> {
+        let base = self.metadata.assets.get(&pair.base)?;
+        let quote = self.metadata.assets.get(&pair.quote)?;
+
+        Some(format!("PF_{}{}", base.altname, quote.altname))
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SymbolRegistry;
+    use crate::Instrument;
+    use std::time::Duration;
+
+    fn registry() -> SymbolRegistry {
+        let assets = serde_json::from_str(
+            r#"{
+                "XXBT": { "aclass": "currency", "altname": "XBT", "decimals": 10, "display_decimals": 5 },
+                "XXDG": { "aclass": "currency", "altname": "XDG", "decimals": 8, "display_decimals": 2 },
+                "ZUSD": { "aclass": "currency", "altname": "USD", "decimals": 4, "display_decimals": 2 },
+                "PYTH": { "aclass": "currency", "altname": "PYTH", "decimals": 10, "display_decimals": 5 }
+            }"#,
+        )
+        .unwrap();
+
+        let pair = |altname: &str, wsname: &str, base: &str| {
+            format!(
+                r#"{{
+                    "altname": "{altname}", "wsname": "{wsname}",
+                    "aclass_base": "currency", "base": "{base}",
+                    "aclass_quote": "currency", "quote": "ZUSD",
+                    "lot": "unit", "pair_decimals": 1, "lot_decimals": 8, "lot_multiplier": 1,
+                    "leverage_buy": [], "leverage_sell": [], "fees": [],
+                    "fee_volume_currency": "ZUSD", "margin_call": 80, "margin_stop": 40
+                }}"#
+            )
+        };
+
+        let pairs = serde_json::from_str(&format!(
+            r#"{{ "XXBTZUSD": {}, "XDGUSD": {}, "PYTHUSD": {} }}"#,
+            pair("XBTUSD", "XBT/USD", "XXBT"),
+            pair("XDGUSD", "XDG/USD", "XXDG"),
+            pair("PYTHUSD", "PYTH/USD", "PYTH"),
+        ))
+        .unwrap();
+
+        SymbolRegistry::from_metadata(assets, pairs)
+    }
+
+    #[test]
+    fn resolve_names() {
+        let registry = registry();
+
+        assert_eq!(registry.asset_code("BTC"), Some("XXBT"));
+        assert_eq!(registry.asset_code("XBT"), Some("XXBT"));
+        assert_eq!(registry.asset_ws_name("XXDG"), Some("DOGE"));
+
+        assert_eq!(registry.pair_name("BTC", "USD"), Some("XXBTZUSD"));
+        assert_eq!(registry.pair_name("PYTH", "USD"), Some("PYTHUSD"));
+        assert_eq!(registry.pair_name("DOGE", "ZUSD"), Some("XDGUSD"));
+
+        assert_eq!(registry.pair_key("BTC/USD"), Some("XXBTZUSD"));
+        assert_eq!(registry.pair_key("XBT/USD"), Some("XXBTZUSD"));
+        assert_eq!(registry.pair_key("PF_XBTUSD"), Some("XXBTZUSD"));
+        assert_eq!(
+            registry.pair_ws_name_of("XDGUSD").as_deref(),
+            Some("DOGE/USD")
+        );
+        assert_eq!(
+            registry.pair_futures_symbol_of("XBTUSD").as_deref(),
+            Some("PF_XBTUSD")
+        );
+    }
+
+    #[test]
+    fn resolve_instruments() {
+        let registry = registry();
+
+        let spot = registry.instrument("XXBTZUSD").unwrap();
+        let futures = registry.instrument("PI_XBTUSD").unwrap();
+
+        assert_eq!(spot, registry.instrument("BTC/USD").unwrap());
+        assert_eq!(spot.to_string(), "BTC/USD");
+        assert!(matches!(futures, Instrument::Futures(_)));
+        assert!(futures.same_market(&spot));
+        assert_eq!(
+            registry.spot_pair("XDGUSD").map(|pair| pair.ws_name()),
+            Some("DOGE/USD".into())
+        );
+        assert_eq!(
+            registry
+                .futures_contract("ff_pythusd_230929")
+                .map(|contract| contract.symbol()),
+            Some("FF_PYTHUSD_230929".into())
+        );
+        assert!(registry.instrument("ETHUSD").is_none());
+    }
+
+    #[test]
+    fn save_and_read_cache() {
+        let registry = registry();
+        let path = std::env::temp_dir().join(format!("symbols-{}.json", std::process::id()));
+
+        registry.save(&path).unwrap();
+
+        let cached = SymbolRegistry::read(&path).unwrap();
+
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(cached.fetched_at(), registry.fetched_at());
+        assert_eq!(cached.pair_key("BTC/USD"), Some("XXBTZUSD"));
+        assert!(!cached.is_older_than(Duration::from_secs(60)));
+    }
+}
@@ -7,6 +7,14 @@ pub use pair_name::*;
 pub mod order;
 pub use order::*;
 
+pub mod order_flags;
+pub use order_flags::*;
+
+pub mod funding;
+pub use funding::*;
+
+pub use kraken_common::instrument::{FuturesContract, FuturesMargin, Instrument, SpotPair};
+
 pub type JsonValue = serde_json::Value;
 
 pub type Userref = i32;
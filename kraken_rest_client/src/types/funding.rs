@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+
+/// The status of a deposit or withdrawal, based on the IFEX financial
+/// transaction states.
+///
+/// - <https://github.com/globalcitizen/ifex-protocol/blob/master/draft-ifex-00.txt#L837>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum FundingStatus {
+    Initial,
+    Pending,
+    Settled,
+    Success,
+    Failure,
+    #[serde(other)]
+    Unknown,
+}
+
+impl FundingStatus {
+    /// Returns true if the transaction reached a final state.
+    pub fn is_final(&self) -> bool {
+        matches!(self, Self::Success | Self::Failure)
+    }
+}
+
+/// Additional status properties of a deposit or withdrawal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum FundingStatusProp {
+    /// Cancelation requested
+    #[serde(rename = "cancel-pending")]
+    CancelPending,
+    /// Canceled
+    #[serde(rename = "canceled")]
+    Canceled,
+    /// Cancelation requested but was denied
+    #[serde(rename = "cancel-denied")]
+    CancelDenied,
+    /// A return transaction initiated by Kraken
+    #[serde(rename = "return")]
+    Return,
+    /// Withdrawal is on hold pending review
+    #[serde(rename = "onhold")]
+    OnHold,
+    #[serde(other)]
+    Unknown,
+}
@@ -1,6 +1,10 @@
 use serde::{Deserialize, Deserializer, Serialize};
 use serde::de::Error;
 use std::fmt;
+use std::str::FromStr;
+
+use crate::util::comma_separated;
+use crate::{OrderFlags, Trigger, Userref};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, Ord, PartialOrd, Hash)]
 pub enum OrderSide {
@@ -31,7 +35,7 @@ impl TryFrom<String> for OrderSide {
     }
 }
 
-fn orderside_from_str<'de, D>(deserializer: D) -> Result<OrderSide, D::Error> where D: Deserializer<'de>, {
+pub(crate) fn orderside_from_str<'de, D>(deserializer: D) -> Result<OrderSide, D::Error> where D: Deserializer<'de>, {
     let orderside = String::deserialize(deserializer)?;
     OrderSide::try_from(orderside).map_err(D::Error::custom)
 }
@@ -90,31 +94,213 @@ impl TryFrom<String> for OrderType {
     }
 }
 
-fn ordertype_from_str<'de, D>(deserializer: D) -> Result<OrderType, D::Error> where D: Deserializer<'de>, {
+pub(crate) fn ordertype_from_str<'de, D>(deserializer: D) -> Result<OrderType, D::Error> where D: Deserializer<'de>, {
     let ordertype = String::deserialize(deserializer)?;
     OrderType::try_from(ordertype).map_err(D::Error::custom)
 }
 
+/// The status of an order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OrderStatus {
+    /// Order pending book entry
+    Pending,
+    /// Open order
+    Open,
+    /// Closed order
+    Closed,
+    /// Order canceled
+    Canceled,
+    /// Order expired
+    Expired,
+    #[serde(other)]
+    Unknown,
+}
+
+impl OrderStatus {
+    /// Returns true if the order left the book and will not change anymore.
+    pub fn is_final(&self) -> bool {
+        matches!(self, Self::Closed | Self::Canceled | Self::Expired)
+    }
+}
+
+/// Additional information about an order, reported in the comma delimited
+/// `misc` field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Ord, PartialOrd, Hash)]
+pub enum OrderMisc {
+    /// Triggered by stop price
+    Stopped,
+    /// Triggered by touch price
+    Touched,
+    /// Liquidation
+    Liquidated,
+    /// Partial fill
+    Partial,
+    /// Order parameters modified
+    Amended,
+}
+
+impl fmt::Display for OrderMisc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let misc = match self {
+            Self::Stopped => "stopped",
+            Self::Touched => "touched",
+            Self::Liquidated => "liquidated",
+            Self::Partial => "partial",
+            Self::Amended => "amended",
+        };
+
+        write!(f, "{}", misc)
+    }
+}
+
+impl FromStr for OrderMisc {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "stopped" => Ok(Self::Stopped),
+            "touched" => Ok(Self::Touched),
+            "liquidated" => Ok(Self::Liquidated),
+            "partial" => Ok(Self::Partial),
+            "amended" => Ok(Self::Amended),
+            _ => Err(format!("Invalid order misc: {}", s)),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct OrderDescription {
-    // pub ordertxid: Option<String>,
-    // pub postxid: Option<String>,
     pub pair: String,
-    // pub time: f64,
     #[serde(rename = "type")]
     #[serde(deserialize_with = "orderside_from_str")]
     pub orderside: OrderSide,
     #[serde(deserialize_with = "ordertype_from_str")]
     pub ordertype: OrderType,
+    /// Primary price
     pub price: String,
+    /// Secondary price
     pub price2: String,
+    /// Amount of leverage
     pub leverage: String,
+    /// Order description
     pub order: String,
+    /// Conditional close order description (if conditional close set)
+    #[serde(default)]
     pub close: String,
-    // TODO: the following fields are missing in some orders:
-    // pub cost: String,
-    // pub fee: String,
-    // pub vol: String,
-    // pub margin: String,
-    // pub misc: String,
+}
+
+/// An order as returned by `OpenOrders`, `ClosedOrders` and `QueryOrders`.
+///
+/// - <https://docs.kraken.com/rest/#tag/Account-Data/operation/getOpenOrders>
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OrderInfo {
+    /// Referral order transaction ID that created this order
+    pub refid: Option<String>,
+    pub userref: Option<Userref>,
+    /// Client order ID
+    pub cl_ord_id: Option<String>,
+    pub status: OrderStatus,
+    /// Why the order was closed or canceled, e.g. `User requested`
+    pub reason: Option<String>,
+    /// Unix timestamp of when the order was placed
+    pub opentm: f64,
+    /// Unix timestamp of when the order was closed, for closed orders
+    pub closetm: Option<f64>,
+    /// Unix timestamp of the order start time, 0 if not set
+    #[serde(default)]
+    pub starttm: f64,
+    /// Unix timestamp of the order end time, 0 if not set
+    #[serde(default)]
+    pub expiretm: f64,
+    pub descr: OrderDescription,
+    /// Volume of the order (base currency)
+    pub vol: String,
+    /// Volume executed (base currency)
+    pub vol_exec: String,
+    /// Total cost (quote currency unless `viqc` is set in `oflags`)
+    pub cost: String,
+    /// Total fee (quote currency)
+    pub fee: String,
+    /// Average price (quote currency)
+    pub price: String,
+    /// Stop price (quote currency)
+    pub stopprice: Option<String>,
+    /// Triggered limit price (quote currency, when limit based order type
+    /// triggered)
+    pub limitprice: Option<String>,
+    /// Price signal used to trigger stop and take-profit orders, missing if
+    /// `last`
+    pub trigger: Option<Trigger>,
+    /// Whether the order is funded on margin
+    pub margin: Option<bool>,
+    #[serde(with = "comma_separated", default)]
+    pub misc: Vec<OrderMisc>,
+    #[serde(default)]
+    pub oflags: OrderFlags,
+    /// IDs of the trades related to the order, if requested with `trades`
+    pub trades: Option<Vec<String>>,
+    /// For institutional accounts, the sub-account that sent the order
+    pub sender_sub_id: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{OrderInfo, OrderMisc, OrderStatus};
+    use crate::{OrderFlag, OrderType, Trigger};
+
+    #[test]
+    fn deserialize_order_info() {
+        let json = r#"{
+            "refid": null,
+            "userref": 0,
+            "status": "closed",
+            "opentm": 1688665496.7808,
+            "closetm": 1688665499.1922,
+            "starttm": 0,
+            "expiretm": 0,
+            "descr": {
+                "pair": "XBTUSD",
+                "type": "sell",
+                "ordertype": "stop-loss-limit",
+                "price": "27500.0",
+                "price2": "27400.0",
+                "leverage": "none",
+                "order": "sell 1.25000000 XBTUSD @ stop loss 27500.0 -> limit 27400.0",
+                "close": ""
+            },
+            "vol": "1.25000000",
+            "vol_exec": "1.25000000",
+            "cost": "34250.0",
+            "fee": "54.8",
+            "price": "27400.0",
+            "stopprice": "27500.0",
+            "limitprice": "27400.0",
+            "trigger": "index",
+            "misc": "stopped,bogus,partial",
+            "oflags": "fciq,post",
+            "trades": ["TCCCTY-WE2O6-P3NB37", "TJUW2K-FLX2N-AR2FLU"]
+        }"#;
+
+        let order: OrderInfo = serde_json::from_str(json).unwrap();
+
+        assert_eq!(order.status, OrderStatus::Closed);
+        assert!(order.status.is_final());
+        assert_eq!(order.closetm, Some(1688665499.1922));
+        assert_eq!(order.descr.ordertype, OrderType::StopLossLimit);
+        assert_eq!(order.trigger, Some(Trigger::Index));
+        assert_eq!(order.misc, [OrderMisc::Stopped, OrderMisc::Partial]);
+        assert!(order.oflags.contains(OrderFlag::Post));
+        assert_eq!(order.trades.unwrap().len(), 2);
+        assert_eq!(order.reason, None);
+
+        let json = serde_json::to_value(OrderInfo {
+            trades: None,
+            ..serde_json::from_str(json).unwrap()
+        })
+        .unwrap();
+
+        assert_eq!(json["misc"], "stopped,partial");
+        assert_eq!(json["oflags"], "post,fciq");
+    }
 }
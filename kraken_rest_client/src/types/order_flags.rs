@@ -0,0 +1,240 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::util::parse_comma_separated;
+
+/// An order flag, sent in the comma delimited `oflags` parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Ord, PartialOrd, Hash)]
+pub enum OrderFlag {
+    /// Post-only order (available when ordertype = limit)
+    Post,
+    /// Prefer fee in base currency (default if selling)
+    Fcib,
+    /// Prefer fee in quote currency (default if buying, mutually exclusive
+    /// with `Fcib`)
+    Fciq,
+    /// Disable market price protection for market orders
+    Nompp,
+    /// Order volume expressed in quote currency (supported only for buy
+    /// market orders)
+    Viqc,
+}
+
+impl fmt::Display for OrderFlag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let flag = match self {
+            Self::Post => "post",
+            Self::Fcib => "fcib",
+            Self::Fciq => "fciq",
+            Self::Nompp => "nompp",
+            Self::Viqc => "viqc",
+        };
+
+        write!(f, "{}", flag)
+    }
+}
+
+impl FromStr for OrderFlag {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "post" => Ok(Self::Post),
+            "fcib" => Ok(Self::Fcib),
+            "fciq" => Ok(Self::Fciq),
+            "nompp" => Ok(Self::Nompp),
+            "viqc" => Ok(Self::Viqc),
+            _ => Err(format!("Invalid order flag: {}", s)),
+        }
+    }
+}
+
+/// A set of order flags. Adding a flag never removes the flags already in
+/// the set.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct OrderFlags(Vec<OrderFlag>);
+
+impl OrderFlags {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the set with `flag` added.
+    pub fn with(mut self, flag: OrderFlag) -> Self {
+        self.insert(flag);
+        self
+    }
+
+    pub fn insert(&mut self, flag: OrderFlag) {
+        if !self.contains(flag) {
+            self.0.push(flag);
+            self.0.sort();
+        }
+    }
+
+    pub fn remove(&mut self, flag: OrderFlag) {
+        self.0.retain(|f| *f != flag);
+    }
+
+    pub fn contains(&self, flag: OrderFlag) -> bool {
+        self.0.contains(&flag)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = OrderFlag> + '_ {
+        self.0.iter().copied()
+    }
+}
+
+impl From<OrderFlag> for OrderFlags {
+    fn from(flag: OrderFlag) -> Self {
+        Self(vec![flag])
+    }
+}
+
+impl<const N: usize> From<[OrderFlag; N]> for OrderFlags {
+    fn from(flags: [OrderFlag; N]) -> Self {
+        flags.into_iter().collect()
+    }
+}
+
+impl FromIterator<OrderFlag> for OrderFlags {
+    fn from_iter<I: IntoIterator<Item = OrderFlag>>(iter: I) -> Self {
+        let mut flags = Self::new();
+
+        for flag in iter {
+            flags.insert(flag);
+        }
+
+        flags
+    }
+}
+
+impl fmt::Display for OrderFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let flags: Vec<String> = self.0.iter().map(OrderFlag::to_string).collect();
+
+        write!(f, "{}", flags.join(","))
+    }
+}
+
+impl FromStr for OrderFlags {
+    type Err = String;
+
+    /// Parses a comma delimited list of order flags.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .filter(|flag| !flag.trim().is_empty())
+            .map(OrderFlag::from_str)
+            .collect()
+    }
+}
+
+impl Serialize for OrderFlags {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for OrderFlags {
+    /// Deserializes the `oflags` of an order, skipping unknown flags.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+
+        Ok(parse_comma_separated(&s))
+    }
+}
+
+/// Time-in-force of the order to specify how long it should remain in the
+/// order book before being cancelled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TimeInForce {
+    /// Good-'til-cancelled is the default if the parameter is omitted.
+    #[default]
+    Gtc,
+    /// Immediate-or-cancel will immediately execute the amount possible and
+    /// cancel any remaining balance rather than resting in the book.
+    Ioc,
+    /// Good-'til-date, must coincide with a desired `expiretm`.
+    Gtd,
+}
+
+impl fmt::Display for TimeInForce {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let timeinforce = match self {
+            Self::Gtc => "GTC",
+            Self::Ioc => "IOC",
+            Self::Gtd => "GTD",
+        };
+
+        write!(f, "{}", timeinforce)
+    }
+}
+
+/// Price signal used to trigger stop-loss, take-profit and trailing-stop
+/// orders.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Trigger {
+    /// The last traded price in the order book, the default.
+    #[default]
+    Last,
+    /// A broader market price, aggregated from external exchanges.
+    Index,
+}
+
+impl fmt::Display for Trigger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let trigger = match self {
+            Self::Last => "last",
+            Self::Index => "index",
+        };
+
+        write!(f, "{}", trigger)
+    }
+}
+
+/// Self trade prevention behavior, applied when two orders from the same user
+/// would match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SelfTradePrevention {
+    /// Arriving order will be canceled, the default.
+    #[default]
+    CancelNewest,
+    /// Resting order will be canceled.
+    CancelOldest,
+    /// Both arriving and resting orders will be canceled.
+    CancelBoth,
+}
+
+impl fmt::Display for SelfTradePrevention {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let stptype = match self {
+            Self::CancelNewest => "cancel-newest",
+            Self::CancelOldest => "cancel-oldest",
+            Self::CancelBoth => "cancel-both",
+        };
+
+        write!(f, "{}", stptype)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{OrderFlag, OrderFlags};
+
+    #[test]
+    fn order_flags_compose() {
+        let flags = OrderFlags::from(OrderFlag::Post)
+            .with(OrderFlag::Fciq)
+            .with(OrderFlag::Post);
+
+        assert_eq!(flags.to_string(), "post,fciq");
+        assert_eq!("fciq, post".parse::<OrderFlags>().unwrap(), flags);
+        assert!("post,bogus".parse::<OrderFlags>().is_err());
+    }
+}
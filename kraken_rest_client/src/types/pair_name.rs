@@ -4,6 +4,9 @@ use super::AssetName;
 use std::fmt::Display;
 
 /// Convenience struct to build a KrakenAPI-compatible pair.
+///
+/// Only knows the legacy codes of a fixed list of assets, use
+/// `SymbolRegistry::pair_name` to resolve pairs from the exchange metadata.
 pub struct PairName {
     base: AssetName,
     quote: AssetName,
@@ -0,0 +1,68 @@
+use serde::{Deserialize, Deserializer};
+use std::str::FromStr;
+
+/// Deserializes a value that the API returns either as a string or as a
+/// number into a string.
+pub(crate) fn string_or_number<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum StringOrNumber {
+        String(String),
+        Number(serde_json::Number),
+    }
+
+    Ok(match StringOrNumber::deserialize(deserializer)? {
+        StringOrNumber::String(value) => value,
+        StringOrNumber::Number(value) => value.to_string(),
+    })
+}
+
+/// Like `string_or_number`, for optional values.
+pub(crate) fn optional_string_or_number<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    struct Wrapper(#[serde(deserialize_with = "string_or_number")] String);
+
+    Ok(Option::<Wrapper>::deserialize(deserializer)?.map(|Wrapper(value)| value))
+}
+
+/// Parses a comma delimited list, e.g. `stopped,amended`. Unknown entries are
+/// skipped, so values added to the API later don't break decoding.
+pub(crate) fn parse_comma_separated<T: FromStr, C: FromIterator<T>>(s: &str) -> C {
+    s.split(',')
+        .filter_map(|value| value.trim().parse().ok())
+        .collect()
+}
+
+/// (De)serializes a collection as a comma delimited list, see
+/// `parse_comma_separated`.
+pub(crate) mod comma_separated {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::fmt::Display;
+    use std::str::FromStr;
+
+    pub(crate) fn serialize<S, T>(values: &[T], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: Display,
+    {
+        let values: Vec<String> = values.iter().map(T::to_string).collect();
+
+        serializer.serialize_str(&values.join(","))
+    }
+
+    pub(crate) fn deserialize<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: FromStr,
+    {
+        let s = String::deserialize(deserializer)?;
+
+        Ok(super::parse_comma_separated(&s))
+    }
+}
@@ -0,0 +1,285 @@
+//! Pre-trade validation of orders against asset pair metadata.
+//!
+//! The exchange rejects orders whose price or volume have more decimals than
+//! the pair allows, or that are below the pair minimums, e.g. with
+//! `EOrder:Invalid price`. `OrderValidator` caches the `AssetPairs` metadata
+//! so these orders can be quantized or rejected locally, before they are
+//! sent.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use rust_decimal::{Decimal, RoundingStrategy};
+
+use crate::{api::PairInfo, error::Error, Client, OrderType, Result};
+
+/// Validates and quantizes order parameters using cached pair metadata.
+#[derive(Debug, Clone, Default)]
+pub struct OrderValidator {
+    pairs: HashMap<String, PairInfo>,
+    /// Maps altnames and websocket names to pair keys.
+    aliases: HashMap<String, String>,
+}
+
+impl OrderValidator {
+    /// Creates a validator from already fetched pair metadata.
+    pub fn from_pairs(pairs: HashMap<String, PairInfo>) -> Self {
+        let mut aliases = HashMap::new();
+
+        for (key, info) in &pairs {
+            aliases.insert(info.altname.clone(), key.clone());
+
+            if let Some(wsname) = &info.wsname {
+                aliases.insert(wsname.clone(), key.clone());
+            }
+        }
+
+        Self { pairs, aliases }
+    }
+
+    /// Fetches the metadata of all tradable pairs.
+    pub async fn load(client: &Client) -> Result<Self> {
+        Ok(Self::from_pairs(client.get_asset_pairs().send().await?))
+    }
+
+    /// Replaces the cached metadata with freshly fetched metadata.
+    pub async fn refresh(&mut self, client: &Client) -> Result<()> {
+        *self = Self::load(client).await?;
+        Ok(())
+    }
+
+    /// Returns the metadata of a pair, looked up by key (`XXBTZUSD`), altname
+    /// (`XBTUSD`) or websocket name (`XBT/USD`).
+    pub fn pair(&self, pair: &str) -> Result<&PairInfo> {
+        let key = self.aliases.get(pair).map(String::as_str).unwrap_or(pair);
+
+        self.pairs
+            .get(key)
+            .ok_or_else(|| Error::invalid_order(format!("unknown pair `{pair}`")))
+    }
+
+    fn tick_size(info: &PairInfo) -> Result<Option<Decimal>> {
+        info.tick_size
+            .as_deref()
+            .map(|tick_size| parse_decimal("tick size", tick_size))
+            .transpose()
+    }
+
+    /// Rounds a price to the nearest valid price level of the pair, midpoints
+    /// away from zero.
+    pub fn round_price(&self, pair: &str, price: Decimal) -> Result<Decimal> {
+        let info = self.pair(pair)?;
+
+        let price = match Self::tick_size(info)? {
+            Some(tick_size) if !tick_size.is_zero() => {
+                let ticks = (price / tick_size)
+                    .round_dp_with_strategy(0, RoundingStrategy::MidpointAwayFromZero);
+
+                ticks * tick_size
+            }
+            _ => price,
+        };
+
+        Ok(price
+            .round_dp_with_strategy(
+                info.pair_decimals as u32,
+                RoundingStrategy::MidpointAwayFromZero,
+            )
+            .normalize())
+    }
+
+    /// Truncates a volume to the lot precision of the pair. Volumes are
+    /// rounded down so that an order never exceeds the intended amount.
+    pub fn round_volume(&self, pair: &str, volume: Decimal) -> Result<Decimal> {
+        let info = self.pair(pair)?;
+
+        Ok(volume
+            .round_dp_with_strategy(info.lot_decimals as u32, RoundingStrategy::ToZero)
+            .normalize())
+    }
+
+    /// Checks that an order respects the precision, minimums and status of
+    /// the pair. Relative prices (e.g. `+1.5`, `#5` or `2%`) are not checked.
+    pub fn check(
+        &self,
+        pair: &str,
+        order_type: OrderType,
+        volume: &str,
+        price: Option<&str>,
+    ) -> Result<()> {
+        let info = self.pair(pair)?;
+
+        match info.status.as_deref() {
+            Some("cancel_only") | Some("delisted") | Some("maintenance") => {
+                return Err(Error::invalid_order(format!(
+                    "pair `{pair}` does not accept new orders"
+                )));
+            }
+            Some("limit_only") | Some("post_only") if order_type == OrderType::Market => {
+                return Err(Error::invalid_order(format!(
+                    "pair `{pair}` does not accept market orders"
+                )));
+            }
+            _ => {}
+        }
+
+        let volume = parse_decimal("volume", volume)?;
+
+        if volume.normalize().scale() > info.lot_decimals as u32 {
+            return Err(Error::invalid_order(format!(
+                "volume {volume} has more than {} decimals",
+                info.lot_decimals
+            )));
+        }
+
+        if let Some(ordermin) = &info.ordermin {
+            let ordermin = parse_decimal("ordermin", ordermin)?;
+
+            if volume < ordermin {
+                return Err(Error::invalid_order(format!(
+                    "volume {volume} is below the minimum {ordermin}"
+                )));
+            }
+        }
+
+        let Some(price) = price.filter(|price| !is_relative_price(price)) else {
+            return Ok(());
+        };
+
+        let price = parse_decimal("price", price)?;
+
+        if price.normalize().scale() > info.pair_decimals as u32 {
+            return Err(Error::invalid_order(format!(
+                "price {price} has more than {} decimals",
+                info.pair_decimals
+            )));
+        }
+
+        if let Some(tick_size) = Self::tick_size(info)? {
+            if !tick_size.is_zero() && !(price % tick_size).is_zero() {
+                return Err(Error::invalid_order(format!(
+                    "price {price} is not a multiple of the tick size {tick_size}"
+                )));
+            }
+        }
+
+        if let Some(costmin) = &info.costmin {
+            let costmin = parse_decimal("costmin", costmin)?;
+            let cost = price * volume;
+
+            if cost < costmin {
+                return Err(Error::invalid_order(format!(
+                    "cost {cost} is below the minimum {costmin}"
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_decimal(name: &str, value: &str) -> Result<Decimal> {
+    Decimal::from_str(value)
+        .or_else(|_| Decimal::from_scientific(value))
+        .map_err(|err| Error::invalid_order(format!("invalid {name} `{value}`: {err}")))
+}
+
+/// Returns true for prices relative to the last traded price, e.g. `+1.5`,
+/// `-2%` or `#5`.
+pub(crate) fn is_relative_price(price: &str) -> bool {
+    price.starts_with(['+', '-', '#']) || price.ends_with('%')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OrderValidator;
+    use crate::{api::GetAssetPairsResponse, Error, OrderType};
+    use rust_decimal::Decimal;
+    use std::str::FromStr;
+
+    fn validator() -> OrderValidator {
+        let json = r#"{
+            "XXBTZUSD": {
+                "altname": "XBTUSD",
+                "wsname": "XBT/USD",
+                "aclass_base": "currency",
+                "base": "XXBT",
+                "aclass_quote": "currency",
+                "quote": "ZUSD",
+                "lot": "unit",
+                "cost_decimals": 5,
+                "pair_decimals": 1,
+                "lot_decimals": 8,
+                "lot_multiplier": 1,
+                "leverage_buy": [2, 3, 4, 5],
+                "leverage_sell": [2, 3, 4, 5],
+                "fees": [[0, 0.26]],
+                "fees_maker": [[0, 0.16]],
+                "fee_volume_currency": "ZUSD",
+                "margin_call": 80,
+                "margin_stop": 40,
+                "ordermin": "0.0001",
+                "costmin": "0.5",
+                "tick_size": "0.1",
+                "status": "online"
+            }
+        }"#;
+
+        let pairs: GetAssetPairsResponse = serde_json::from_str(json).unwrap();
+
+        OrderValidator::from_pairs(pairs)
+    }
+
+    #[test]
+    fn quantize_price_and_volume() {
+        let validator = validator();
+
+        let price = validator
+            .round_price("XBT/USD", Decimal::from_str("30000.06").unwrap())
+            .unwrap();
+        let volume = validator
+            .round_volume("XBTUSD", Decimal::from_str("0.123456789").unwrap())
+            .unwrap();
+
+        assert_eq!(price.to_string(), "30000.1");
+        assert_eq!(volume.to_string(), "0.12345678");
+
+        for (price, rounded) in [("30000.05", "30000.1"), ("30000.15", "30000.2")] {
+            let price = validator
+                .round_price("XBTUSD", Decimal::from_str(price).unwrap())
+                .unwrap();
+
+            assert_eq!(price.to_string(), rounded);
+        }
+    }
+
+    #[test]
+    fn check_order_against_pair() {
+        let validator = validator();
+
+        assert!(validator
+            .check("XXBTZUSD", OrderType::Limit, "0.01", Some("30000.1"))
+            .is_ok());
+        assert!(validator
+            .check("XXBTZUSD", OrderType::Limit, "0.01", Some("+1.5%"))
+            .is_ok());
+
+        let invalid = [
+            ("0.01", "30000.05"),
+            ("0.00001", "30000.0"),
+            ("0.123456789", "30000.0"),
+            ("0.0001", "100.0"),
+        ];
+
+        for (volume, price) in invalid {
+            assert!(matches!(
+                validator.check("XXBTZUSD", OrderType::Limit, volume, Some(price)),
+                Err(Error::InvalidOrder(_))
+            ));
+        }
+
+        assert!(validator
+            .check("XETHZUSD", OrderType::Market, "1", None)
+            .is_err());
+    }
+}
@@ -10,6 +10,7 @@ mod util;
 
 pub use client::{PrivateClient, PublicClient};
 pub use error::Error;
+pub use kraken_common::instrument::{FuturesContract, FuturesMargin, Instrument, SpotPair};
 pub use util::Result;
 
 pub async fn connect_public() -> Result<PublicClient> {